@@ -1,7 +1,5 @@
-
 // 与 Raydium 恒定乘积(CP)自动做市商(AMM)智能合约交互的客户端实现，主要包含四个核心功能：初始化资金池、存款、取款和交换代币。
 
-
 // PDA(Program Derived Address): 使用种子和程序ID派生的特殊地址，确保只有特定程序可以签名
 // Token Vaults: 池中存储代币的账户
 // LP Token: 流动性提供者代币，代表池中的份额
@@ -10,12 +8,16 @@
 // anchor_client 用于与Anchor程序交互的客户端库
 use anchor_client::{Client, Cluster};
 use anchor_spl::{
-    associated_token::spl_associated_token_account, memo::spl_memo, token::spl_token,
-    token_2022::spl_token_2022,
+    associated_token::spl_associated_token_account, memo::spl_memo, metadata::mpl_token_metadata,
+    token::spl_token, token_2022::spl_token_2022,
 };
 use anyhow::Result;
 // 基础SDK，包含指令、公钥等核心类型
-use solana_sdk::{instruction::Instruction, pubkey::Pubkey, system_program, sysvar};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
 
 // 引入Raydium程序自动生成的账户结构和指令结构
 use raydium_cp_swap::accounts as raydium_cp_accounts;
@@ -23,7 +25,12 @@ use raydium_cp_swap::instruction as raydium_cp_instructions;
 
 // 引入程序中定义的常量种子(seeds)，用于PDA派生
 use raydium_cp_swap::{
-    states::{AMM_CONFIG_SEED, OBSERVATION_SEED, POOL_LP_MINT_SEED, POOL_SEED, POOL_VAULT_SEED},
+    states::{
+        FeeDiscount, PoolGuardBitIndex, PoolHook, PoolState, PriceGuard, AMM_CONFIG_SEED,
+        CIRCUIT_BREAKER_SEED, FEE_DISCOUNT_LOCK_SEED, FEE_DISCOUNT_SEED, LP_METADATA_CONFIG_SEED,
+        MINT_POLICY_SEED, OBSERVATION_SEED, POOL_HOOK_SEED, POOL_LP_MINT_SEED, POOL_SEED,
+        POOL_VAULT_SEED, PRICE_FEED_SEED, PRICE_GUARD_SEED,
+    },
     AUTH_SEED,
 };
 use std::rc::Rc;
@@ -125,16 +132,35 @@ pub fn initialize_pool_instr(
         &program.id(),
     );
 
+    // 计算铸币策略地址
+    let mint_policy_key =
+        Pubkey::find_program_address(&[MINT_POLICY_SEED.as_bytes()], &program.id()).0;
+
+    // 计算 LP 元数据配置地址和 LP 铸币的元数据地址
+    let lp_metadata_config_key =
+        Pubkey::find_program_address(&[LP_METADATA_CONFIG_SEED.as_bytes()], &program.id()).0;
+    let lp_mint_metadata_key = mpl_token_metadata::accounts::Metadata::find_pda(&lp_mint_key).0;
+
     let mut instructions = program
         .request()
         .accounts(raydium_cp_accounts::Initialize {
             creator: program.payer(),   // 创建者，支付交易费用的账户
             amm_config: amm_config_key, //amm 配置账户
-            authority,                  //pool 的管理权限PDA
+            mint_policy: mint_policy_key,
+            lp_metadata_config: lp_metadata_config_key,
+            authority, //pool 的管理权限PDA
             pool_state: pool_account_key,
             token_0_mint,
             token_1_mint,
+            // 代币的 metaplex 元数据帐户，不存在时使用 token_2022 元数据扩展或铸币地址生成 LP 名称
+            token_0_metadata: Some(
+                mpl_token_metadata::accounts::Metadata::find_pda(&token_0_mint).0,
+            ),
+            token_1_metadata: Some(
+                mpl_token_metadata::accounts::Metadata::find_pda(&token_1_mint).0,
+            ),
             lp_mint: lp_mint_key,
+            lp_mint_metadata: lp_mint_metadata_key,
             creator_token_0: user_token_0_account,
             creator_token_1: user_token_1_account,
             creator_lp_token: spl_associated_token_account::get_associated_token_address(
@@ -149,6 +175,7 @@ pub fn initialize_pool_instr(
             token_0_program,
             token_1_program,
             associated_token_program: spl_associated_token_account::id(),
+            metadata_program: mpl_token_metadata::ID,
             system_program: system_program::id(), //必须的系统程序
             rent: sysvar::rent::id(),             //租金系统变量
         })
@@ -179,12 +206,14 @@ pub fn deposit_instr(
     token_lp_mint: Pubkey, // LP mint地址
     token_0_vault: Pubkey, //
     token_1_vault: Pubkey, //
+    observation_account: Pubkey,
     user_token_0_account: Pubkey,
     user_token_1_account: Pubkey,
     user_token_lp_account: Pubkey,
     lp_token_amount: u64,        // 期望获得的LP代币数量
     maximum_token_0_amount: u64, // 代币A的最大存入量
     maximum_token_1_amount: u64, // 代币B的最大存入量
+    remaining_accounts: Vec<AccountMeta>,
 ) -> Result<Vec<Instruction>> {
     let payer = read_keypair_file(&config.payer_path)?;
     let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
@@ -210,12 +239,14 @@ pub fn deposit_instr(
             vault_0_mint: token_0_mint,
             vault_1_mint: token_1_mint,
             lp_mint: token_lp_mint,
+            observation_state: observation_account,
         })
         .args(raydium_cp_instructions::Deposit {
             lp_token_amount,        // 期望获得的LP代币数量
             maximum_token_0_amount, // 代币A的最大存入量
             maximum_token_1_amount, // 代币B的最大存入量
         })
+        .accounts(remaining_accounts)
         .instructions()?;
     Ok(instructions)
 }
@@ -229,12 +260,14 @@ pub fn withdraw_instr(
     token_lp_mint: Pubkey,
     token_0_vault: Pubkey,
     token_1_vault: Pubkey,
+    observation_account: Pubkey,
     user_token_0_account: Pubkey,
     user_token_1_account: Pubkey,
     user_token_lp_account: Pubkey,
     lp_token_amount: u64,
     minimum_token_0_amount: u64,
     minimum_token_1_amount: u64,
+    remaining_accounts: Vec<AccountMeta>,
 ) -> Result<Vec<Instruction>> {
     let payer = read_keypair_file(&config.payer_path)?;
     let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
@@ -261,16 +294,213 @@ pub fn withdraw_instr(
             vault_1_mint: token_1_mint,
             lp_mint: token_lp_mint,
             memo_program: spl_memo::id(),
+            observation_state: observation_account,
         })
         .args(raydium_cp_instructions::Withdraw {
             lp_token_amount,
             minimum_token_0_amount,
             minimum_token_1_amount,
         })
+        .accounts(remaining_accounts)
         .instructions()?;
     Ok(instructions)
 }
 
+// 更新价格观察值
+pub fn observe_pool_instr(
+    config: &ClientConfig,
+    pool_id: Pubkey,
+    token_0_vault: Pubkey,
+    token_1_vault: Pubkey,
+    observation_account: Pubkey,
+    price_feed: Option<Pubkey>,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_cp_program)?;
+
+    // 价格源通过剩余账户传入
+    let remaining_accounts: Vec<AccountMeta> = price_feed
+        .into_iter()
+        .map(|price_feed| AccountMeta::new(price_feed, false))
+        .collect();
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::ObservePool {
+            payer: program.payer(),
+            pool_state: pool_id,
+            token_0_vault,
+            token_1_vault,
+            observation_state: observation_account,
+        })
+        .accounts(remaining_accounts)
+        .args(raydium_cp_instructions::ObservePool {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+// 扩展池的观察帐户以记录累计价格刻度
+pub fn extend_observation_instr(
+    config: &ClientConfig,
+    pool_id: Pubkey,
+    observation_account: Pubkey,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_cp_program)?;
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::ExtendObservation {
+            payer: program.payer(),
+            pool_state: pool_id,
+            observation_state: observation_account,
+            system_program: system_program::id(),
+        })
+        .args(raydium_cp_instructions::ExtendObservation {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+// 创建池的价格源
+pub fn create_price_feed_instr(
+    config: &ClientConfig,
+    pool_id: Pubkey,
+    twap_window: u64,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_cp_program)?;
+
+    let price_feed = Pubkey::find_program_address(
+        &[PRICE_FEED_SEED.as_bytes(), pool_id.to_bytes().as_ref()],
+        &program.id(),
+    )
+    .0;
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::CreatePriceFeed {
+            authority: program.payer(),
+            pool_state: pool_id,
+            price_feed,
+            system_program: system_program::id(),
+        })
+        .args(raydium_cp_instructions::CreatePriceFeed { twap_window })
+        .instructions()?;
+    Ok(instructions)
+}
+
+// 交换需要的钩子、交易费折扣、熔断器、价格源和价格保护帐户
+pub fn swap_guard_accounts(
+    config: &ClientConfig,
+    pool_id: Pubkey,
+    pool_state: &PoolState,
+) -> Result<Vec<AccountMeta>> {
+    let mut remaining_accounts = pool_hook_accounts(config, pool_id)?;
+    remaining_accounts.extend(fee_discount_accounts(config, pool_state.amm_config)?);
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_cp_program)?;
+
+    if pool_state.get_guard_by_bit(PoolGuardBitIndex::CircuitBreaker) {
+        let circuit_breaker_key = Pubkey::find_program_address(
+            &[CIRCUIT_BREAKER_SEED.as_bytes(), pool_id.to_bytes().as_ref()],
+            &program.id(),
+        )
+        .0;
+        remaining_accounts.push(AccountMeta::new_readonly(circuit_breaker_key, false));
+    }
+    if pool_state.get_guard_by_bit(PoolGuardBitIndex::PriceFeed) {
+        let price_feed_key = Pubkey::find_program_address(
+            &[PRICE_FEED_SEED.as_bytes(), pool_id.to_bytes().as_ref()],
+            &program.id(),
+        )
+        .0;
+        remaining_accounts.push(AccountMeta::new(price_feed_key, false));
+    }
+    if !pool_state.get_guard_by_bit(PoolGuardBitIndex::Price) {
+        return Ok(remaining_accounts);
+    }
+
+    let price_guard_key = Pubkey::find_program_address(
+        &[PRICE_GUARD_SEED.as_bytes(), pool_id.to_bytes().as_ref()],
+        &program.id(),
+    )
+    .0;
+    let price_guard: PriceGuard = program.account(price_guard_key)?;
+    remaining_accounts.push(AccountMeta::new_readonly(price_guard_key, false));
+    remaining_accounts.push(AccountMeta::new_readonly(
+        price_guard.reference_price,
+        false,
+    ));
+    Ok(remaining_accounts)
+}
+
+// 交易费折扣账户及付款人的折扣代币锁定账户，配置没有交易费折扣时为空
+pub fn fee_discount_accounts(
+    config: &ClientConfig,
+    amm_config: Pubkey,
+) -> Result<Vec<AccountMeta>> {
+    let mut remaining_accounts = Vec::new();
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_cp_program)?;
+
+    let fee_discount_key = Pubkey::find_program_address(
+        &[FEE_DISCOUNT_SEED.as_bytes(), amm_config.to_bytes().as_ref()],
+        &program.id(),
+    )
+    .0;
+    if program.account::<FeeDiscount>(fee_discount_key).is_err() {
+        return Ok(remaining_accounts);
+    }
+    let fee_discount_lock_key = Pubkey::find_program_address(
+        &[
+            FEE_DISCOUNT_LOCK_SEED.as_bytes(),
+            fee_discount_key.to_bytes().as_ref(),
+            program.payer().to_bytes().as_ref(),
+        ],
+        &program.id(),
+    )
+    .0;
+    remaining_accounts.push(AccountMeta::new_readonly(fee_discount_key, false));
+    remaining_accounts.push(AccountMeta::new_readonly(fee_discount_lock_key, false));
+    Ok(remaining_accounts)
+}
+
+// 池的钩子账户，池注册了钩子程序时交换、存款和取款需要传入
+pub fn pool_hook_accounts(config: &ClientConfig, pool_id: Pubkey) -> Result<Vec<AccountMeta>> {
+    let mut remaining_accounts = Vec::new();
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.raydium_cp_program)?;
+
+    let pool_hook_key = Pubkey::find_program_address(
+        &[POOL_HOOK_SEED.as_bytes(), pool_id.to_bytes().as_ref()],
+        &program.id(),
+    )
+    .0;
+    let pool_hook = match program.account::<PoolHook>(pool_hook_key) {
+        Ok(pool_hook) if pool_hook.policy != 0 => pool_hook,
+        _ => return Ok(remaining_accounts),
+    };
+    remaining_accounts.push(AccountMeta::new_readonly(pool_hook_key, false));
+    remaining_accounts.push(AccountMeta::new_readonly(pool_hook.hook_program, false));
+    Ok(remaining_accounts)
+}
+
 // 交换操作
 pub fn swap_base_input_instr(
     config: &ClientConfig,
@@ -287,6 +517,7 @@ pub fn swap_base_input_instr(
     output_token_program: Pubkey,
     amount_in: u64,
     minimum_amount_out: u64,
+    remaining_accounts: Vec<AccountMeta>,
 ) -> Result<Vec<Instruction>> {
     let payer = read_keypair_file(&config.payer_path)?;
     let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
@@ -312,7 +543,9 @@ pub fn swap_base_input_instr(
             input_token_mint,
             output_token_mint,
             observation_state: observation_account,
+            referrer_token_account: None,
         })
+        .accounts(remaining_accounts)
         .args(raydium_cp_instructions::SwapBaseInput {
             amount_in,          // 精确输入量
             minimum_amount_out, // 可接受的最小输出
@@ -337,6 +570,7 @@ pub fn swap_base_output_instr(
     output_token_program: Pubkey,
     max_amount_in: u64,
     amount_out: u64,
+    remaining_accounts: Vec<AccountMeta>,
 ) -> Result<Vec<Instruction>> {
     let payer = read_keypair_file(&config.payer_path)?;
     let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
@@ -362,7 +596,9 @@ pub fn swap_base_output_instr(
             input_token_mint,
             output_token_mint,
             observation_state: observation_account,
+            referrer_token_account: None,
         })
+        .accounts(remaining_accounts)
         .args(raydium_cp_instructions::SwapBaseOutput {
             max_amount_in,
             amount_out,
@@ -154,6 +154,9 @@ pub fn handle_program_log(
             LpChangeEvent::DISCRIMINATOR => {
                 println!("{:#?}", decode_event::<LpChangeEvent>(&mut slice)?);
             }
+            CircuitBreakerEvent::DISCRIMINATOR => {
+                println!("{:#?}", decode_event::<CircuitBreakerEvent>(&mut slice)?);
+            }
             _ => {
                 println!("unknow event: {}", l);
             }
@@ -184,8 +184,8 @@ pub fn create_and_init_auxiliary_token(
 pub fn close_token_account(
     config: &ClientConfig,
     close_account: &Pubkey, // 要关闭的账户
-    destination: &Pubkey, // 接收剩余SOL的账户
-    owner: &Keypair, // 账户所有者密钥
+    destination: &Pubkey,   // 接收剩余SOL的账户
+    owner: &Keypair,        // 账户所有者密钥
 ) -> Result<Vec<Instruction>> {
     let payer = read_keypair_file(&config.payer_path)?;
     let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
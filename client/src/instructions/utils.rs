@@ -8,6 +8,8 @@ use anchor_spl::token_2022::spl_token_2022::{
 };
 use anyhow::Result;
 use bytemuck::Pod;
+use raydium_cp_swap::curve::{SwapResult, TradeDirection};
+use raydium_cp_swap::states::{price_impact_bps, AmmConfig};
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{account::Account as CliAccount, pubkey::Pubkey};
 use std::ops::Mul;
@@ -42,6 +44,63 @@ pub fn amount_with_slippage(amount: u64, slippage: f64, round_up: bool) -> u64 {
     }
 }
 
+/// Warn the swap would be rejected for exceeding the max price impact of config,
+/// the reserves are before the swap
+pub fn warn_price_impact(
+    amm_config: &AmmConfig,
+    trade_direction: TradeDirection,
+    fee_on_output: bool,
+    total_input_token_amount: u64,
+    total_output_token_amount: u64,
+    result: &SwapResult,
+) {
+    if amm_config.max_price_impact_bps == 0 {
+        return;
+    }
+    // the protocol and fund fees are not in the reserves after the swap
+    let fees = result.protocol_fee + result.fund_fee;
+    let (new_input_amount, new_output_amount) = if fee_on_output {
+        (
+            result.new_swap_source_amount,
+            result.new_swap_destination_amount.saturating_sub(fees),
+        )
+    } else {
+        (
+            result.new_swap_source_amount.saturating_sub(fees),
+            result.new_swap_destination_amount,
+        )
+    };
+    let token_price_x32 = |token_0_amount: u128, token_1_amount: u128| {
+        (
+            (token_1_amount << 32) / token_0_amount,
+            (token_0_amount << 32) / token_1_amount,
+        )
+    };
+    let (price_before_x32, price_after_x32) = match trade_direction {
+        TradeDirection::ZeroForOne => (
+            token_price_x32(
+                u128::from(total_input_token_amount),
+                u128::from(total_output_token_amount),
+            ),
+            token_price_x32(new_input_amount, new_output_amount),
+        ),
+        TradeDirection::OneForZero => (
+            token_price_x32(
+                u128::from(total_output_token_amount),
+                u128::from(total_input_token_amount),
+            ),
+            token_price_x32(new_output_amount, new_input_amount),
+        ),
+    };
+    let price_impact_bps = price_impact_bps(price_before_x32, price_after_x32);
+    if price_impact_bps > amm_config.max_price_impact_bps {
+        println!(
+            "warning: price impact {} bps exceeds the max price impact {} bps, the swap will be rejected",
+            price_impact_bps, amm_config.max_price_impact_bps
+        );
+    }
+}
+
 pub fn get_pool_mints_inverse_fee(
     rpc_client: &RpcClient,
     token_mint_0: Pubkey,
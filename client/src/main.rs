@@ -120,6 +120,29 @@ pub enum RaydiumCpCommands {
         user_input_token: Pubkey,
         amount_out_less_fee: u64,
     },
+    PoolStats {
+        pool_id: Pubkey,
+    },
+    LpFeesEarned {
+        pool_id: Pubkey,
+        lp_amount: u64,
+        fee_growth_global_0_x64: u128,
+        fee_growth_global_1_x64: u128,
+    },
+    Twap {
+        pool_id: Pubkey,
+        window: u64,
+    },
+    ObservePool {
+        pool_id: Pubkey,
+    },
+    ExtendObservation {
+        pool_id: Pubkey,
+    },
+    CreatePriceFeed {
+        pool_id: Pubkey,
+        twap_window: u64,
+    },
     DecodeInstruction {
         instr_hex_data: String,
     },
@@ -294,6 +317,7 @@ fn main() -> Result<()> {
                 pool_state.lp_mint,
                 pool_state.token_0_vault,
                 pool_state.token_1_vault,
+                pool_state.observation_key,
                 user_token_0,
                 user_token_1,
                 spl_associated_token_account::get_associated_token_address(
@@ -303,6 +327,7 @@ fn main() -> Result<()> {
                 lp_token_amount,
                 amount_0_max,
                 amount_1_max,
+                pool_hook_accounts(&pool_config, pool_id)?,
             )?;
             instructions.extend(deposit_instr);
             let signers = vec![&payer];
@@ -398,6 +423,7 @@ fn main() -> Result<()> {
                 &payer.pubkey(),
             )?;
             instructions.extend(create_user_token_1_instr);
+            let remaining_accounts = pool_hook_accounts(&pool_config, pool_id)?;
             let withdraw_instr = withdraw_instr(
                 &pool_config,
                 pool_id,
@@ -406,6 +432,7 @@ fn main() -> Result<()> {
                 pool_state.lp_mint,
                 pool_state.token_0_vault,
                 pool_state.token_1_vault,
+                pool_state.observation_key,
                 spl_associated_token_account::get_associated_token_address(
                     &payer.pubkey(),
                     &pool_state.token_0_mint,
@@ -418,6 +445,7 @@ fn main() -> Result<()> {
                 lp_token_amount,
                 amount_0_min,
                 amount_1_min,
+                remaining_accounts,
             )?;
             instructions.extend(withdraw_instr);
             let signers = vec![&payer];
@@ -450,6 +478,8 @@ fn main() -> Result<()> {
             ];
             let rsps = rpc_client.get_multiple_accounts(&load_pubkeys)?;
             let epoch = rpc_client.get_epoch_info().unwrap().epoch;
+            // the trade fee rate of pool is higher during the launch phase
+            let block_timestamp = rpc_client.get_block_time(rpc_client.get_slot()?)? as u64;
             let [pool_account, amm_config_account, token_0_vault_account, token_1_vault_account, token_0_mint_account, token_1_mint_account, user_input_token_account] =
                 array_ref![rsps, 0, 7];
             // docode account
@@ -525,16 +555,43 @@ fn main() -> Result<()> {
             };
             // Take transfer fees into account for actual amount transferred in
             let actual_amount_in = user_input_amount.saturating_sub(transfer_fee);
-            let result = raydium_cp_swap::curve::CurveCalculator::swap_base_input(
-                u128::from(actual_amount_in),
-                u128::from(total_input_token_amount),
-                u128::from(total_output_token_amount),
-                amm_config_state.trade_fee_rate,
-                amm_config_state.protocol_fee_rate,
-                amm_config_state.fund_fee_rate,
-            )
+            let result = if pool_state.fee_on_output(trade_direction) {
+                raydium_cp_swap::curve::CurveCalculator::swap_base_input_with_output_fee(
+                    u128::from(actual_amount_in),
+                    u128::from(total_input_token_amount),
+                    u128::from(total_output_token_amount),
+                    pool_state.trade_fee_rate(
+                        amm_config_state.trade_fee_rate,
+                        trade_direction,
+                        block_timestamp,
+                    ),
+                    amm_config_state.protocol_fee_rate,
+                    amm_config_state.fund_fee_rate,
+                )
+            } else {
+                raydium_cp_swap::curve::CurveCalculator::swap_base_input(
+                    u128::from(actual_amount_in),
+                    u128::from(total_input_token_amount),
+                    u128::from(total_output_token_amount),
+                    pool_state.trade_fee_rate(
+                        amm_config_state.trade_fee_rate,
+                        trade_direction,
+                        block_timestamp,
+                    ),
+                    amm_config_state.protocol_fee_rate,
+                    amm_config_state.fund_fee_rate,
+                )
+            }
             .ok_or(raydium_cp_swap::error::ErrorCode::ZeroTradingTokens)
             .unwrap();
+            warn_price_impact(
+                &amm_config_state,
+                trade_direction,
+                pool_state.fee_on_output(trade_direction),
+                total_input_token_amount,
+                total_output_token_amount,
+                &result,
+            );
             let amount_out = u64::try_from(result.destination_amount_swapped).unwrap();
             let transfer_fee = match trade_direction {
                 raydium_cp_swap::curve::TradeDirection::ZeroForOne => {
@@ -572,6 +629,7 @@ fn main() -> Result<()> {
                 output_token_program,
                 user_input_amount,
                 minimum_amount_out,
+                swap_guard_accounts(&pool_config, pool_id, &pool_state)?,
             )?;
             instructions.extend(swap_base_in_instr);
             let signers = vec![&payer];
@@ -604,6 +662,8 @@ fn main() -> Result<()> {
             ];
             let rsps = rpc_client.get_multiple_accounts(&load_pubkeys)?;
             let epoch = rpc_client.get_epoch_info().unwrap().epoch;
+            // the trade fee rate of pool is higher during the launch phase
+            let block_timestamp = rpc_client.get_block_time(rpc_client.get_slot()?)? as u64;
             let [pool_account, amm_config_account, token_0_vault_account, token_1_vault_account, token_0_mint_account, token_1_mint_account, user_input_token_account] =
                 array_ref![rsps, 0, 7];
             // docode account
@@ -679,16 +739,43 @@ fn main() -> Result<()> {
             };
             let actual_amount_out = amount_out_less_fee.checked_add(out_transfer_fee).unwrap();
 
-            let result = raydium_cp_swap::curve::CurveCalculator::swap_base_output(
-                u128::from(actual_amount_out),
-                u128::from(total_input_token_amount),
-                u128::from(total_output_token_amount),
-                amm_config_state.trade_fee_rate,
-                amm_config_state.protocol_fee_rate,
-                amm_config_state.fund_fee_rate,
-            )
+            let result = if pool_state.fee_on_output(trade_direction) {
+                raydium_cp_swap::curve::CurveCalculator::swap_base_output_with_output_fee(
+                    u128::from(actual_amount_out),
+                    u128::from(total_input_token_amount),
+                    u128::from(total_output_token_amount),
+                    pool_state.trade_fee_rate(
+                        amm_config_state.trade_fee_rate,
+                        trade_direction,
+                        block_timestamp,
+                    ),
+                    amm_config_state.protocol_fee_rate,
+                    amm_config_state.fund_fee_rate,
+                )
+            } else {
+                raydium_cp_swap::curve::CurveCalculator::swap_base_output(
+                    u128::from(actual_amount_out),
+                    u128::from(total_input_token_amount),
+                    u128::from(total_output_token_amount),
+                    pool_state.trade_fee_rate(
+                        amm_config_state.trade_fee_rate,
+                        trade_direction,
+                        block_timestamp,
+                    ),
+                    amm_config_state.protocol_fee_rate,
+                    amm_config_state.fund_fee_rate,
+                )
+            }
             .ok_or(raydium_cp_swap::error::ErrorCode::ZeroTradingTokens)
             .unwrap();
+            warn_price_impact(
+                &amm_config_state,
+                trade_direction,
+                pool_state.fee_on_output(trade_direction),
+                total_input_token_amount,
+                total_output_token_amount,
+                &result,
+            );
 
             let source_amount_swapped = u64::try_from(result.source_amount_swapped).unwrap();
             let amount_in_transfer_fee = match trade_direction {
@@ -729,6 +816,7 @@ fn main() -> Result<()> {
                 output_token_program,
                 max_amount_in,
                 amount_out_less_fee,
+                swap_guard_accounts(&pool_config, pool_id, &pool_state)?,
             )?;
             instructions.extend(swap_base_in_instr);
             let signers = vec![&payer];
@@ -742,6 +830,137 @@ fn main() -> Result<()> {
             let signature = send_txn(&rpc_client, &txn, true)?;
             println!("{}", signature);
         }
+        RaydiumCpCommands::PoolStats { pool_id } => {
+            let pool_state: raydium_cp_swap::states::PoolState = program.account(pool_id)?;
+            let slot = rpc_client.get_slot()?;
+            let block_time = rpc_client.get_block_time(slot)?;
+            // take two snapshots to get the figures over a period, such as 24h
+            println!("slot: {}, block_time: {}", slot, block_time);
+            println!(
+                "cumulative_volume_token_0: {}, cumulative_volume_token_1: {}",
+                { pool_state.cumulative_volume_token_0 },
+                { pool_state.cumulative_volume_token_1 }
+            );
+            println!(
+                "cumulative_lp_fees_token_0: {}, cumulative_lp_fees_token_1: {}",
+                { pool_state.cumulative_lp_fees_token_0 },
+                { pool_state.cumulative_lp_fees_token_1 }
+            );
+            println!("swap_count: {}", { pool_state.swap_count });
+            println!(
+                "fee_growth_global_0_x64: {}, fee_growth_global_1_x64: {}",
+                { pool_state.fee_growth_global_0_x64 },
+                { pool_state.fee_growth_global_1_x64 }
+            );
+        }
+        RaydiumCpCommands::LpFeesEarned {
+            pool_id,
+            lp_amount,
+            fee_growth_global_0_x64,
+            fee_growth_global_1_x64,
+        } => {
+            // the fee growth of the snapshot taken by pool-stats
+            let pool_state: raydium_cp_swap::states::PoolState = program.account(pool_id)?;
+            let fees_earned_0 = raydium_cp_swap::states::lp_fees_earned(
+                lp_amount,
+                fee_growth_global_0_x64,
+                pool_state.fee_growth_global_0_x64,
+            );
+            let fees_earned_1 = raydium_cp_swap::states::lp_fees_earned(
+                lp_amount,
+                fee_growth_global_1_x64,
+                pool_state.fee_growth_global_1_x64,
+            );
+            println!(
+                "fees_earned_token_0: {}, fees_earned_token_1: {}",
+                fees_earned_0, fees_earned_1
+            );
+        }
+        RaydiumCpCommands::Twap { pool_id, window } => {
+            let pool_state: raydium_cp_swap::states::PoolState = program.account(pool_id)?;
+            let observation_state: raydium_cp_swap::states::ObservationState =
+                program.account(pool_state.observation_key)?;
+            // the cumulative ticks are stored behind the observation state
+            let observation_ticks =
+                raydium_cp_swap::states::ObservationTicks::try_from_account_data(
+                    &rpc_client.get_account_data(&pool_state.observation_key)?,
+                );
+            match observation_state.get_twap_tick(observation_ticks.as_ref(), window) {
+                Some(tick) => {
+                    // the geometric mean price of token_0 in token_1 without decimals
+                    let price_x64 = raydium_cp_swap::states::price_x64_at_tick(tick);
+                    println!(
+                        "twap_tick: {}, twap_price: {}",
+                        tick,
+                        price_x64 as f64 / (1u128 << 64) as f64
+                    );
+                }
+                None => println!("not enough observations for the window"),
+            }
+        }
+        RaydiumCpCommands::ObservePool { pool_id } => {
+            let pool_state: raydium_cp_swap::states::PoolState = program.account(pool_id)?;
+            // refresh the price feed of the pool as well if it was created
+            let price_feed = Pubkey::find_program_address(
+                &[
+                    raydium_cp_swap::states::PRICE_FEED_SEED.as_bytes(),
+                    pool_id.to_bytes().as_ref(),
+                ],
+                &pool_config.raydium_cp_program,
+            )
+            .0;
+            let price_feed = rpc_client.get_account(&price_feed).ok().map(|_| price_feed);
+            let observe_pool_instr = observe_pool_instr(
+                &pool_config,
+                pool_id,
+                pool_state.token_0_vault,
+                pool_state.token_1_vault,
+                pool_state.observation_key,
+                price_feed,
+            )?;
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &observe_pool_instr,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::ExtendObservation { pool_id } => {
+            let pool_state: raydium_cp_swap::states::PoolState = program.account(pool_id)?;
+            let extend_observation_instr =
+                extend_observation_instr(&pool_config, pool_id, pool_state.observation_key)?;
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &extend_observation_instr,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::CreatePriceFeed {
+            pool_id,
+            twap_window,
+        } => {
+            let create_price_feed_instr =
+                create_price_feed_instr(&pool_config, pool_id, twap_window)?;
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &create_price_feed_instr,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
         RaydiumCpCommands::DecodeInstruction { instr_hex_data } => {
             handle_program_instruction(&instr_hex_data, InstructionDecodeType::BaseHex)?;
         }
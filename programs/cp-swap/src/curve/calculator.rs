@@ -65,11 +65,11 @@ pub struct SwapResult {
     pub source_amount_swapped: u128,
     /// 交换的目标代币数量
     pub destination_amount_swapped: u128,
-    /// 流向池持有者的源代币数量
+    /// 流向池持有者的源代币数量，输出端收费时为目的地代币数量
     pub trade_fee: u128,
-    /// 进入协议的源代币数量
+    /// 进入协议的源代币数量，输出端收费时为目的地代币数量
     pub protocol_fee: u128,
-    /// 流向协议团队的源代币数量
+    /// 流向协议团队的源代币数量，输出端收费时为目的地代币数量
     pub fund_fee: u128,
 }
 
@@ -155,6 +155,77 @@ impl CurveCalculator {
         })
     }
 
+    /// 计算给定数量的源代币将提供多少目的地代币，
+    /// 费用从目的地代币中扣除。
+    pub fn swap_base_input_with_output_fee(
+        source_amount: u128,
+        swap_source_amount: u128,
+        swap_destination_amount: u128,
+        trade_fee_rate: u64,
+        protocol_fee_rate: u64,
+        fund_fee_rate: u64,
+    ) -> Option<SwapResult> {
+        let destination_amount_before_fees = ConstantProductCurve::swap_base_input_without_fees(
+            source_amount,
+            swap_source_amount,
+            swap_destination_amount,
+        );
+
+        let trade_fee = Fees::trading_fee(destination_amount_before_fees, trade_fee_rate)?;
+        let protocol_fee = Fees::protocol_fee(trade_fee, protocol_fee_rate)?;
+        let fund_fee = Fees::fund_fee(trade_fee, fund_fee_rate)?;
+
+        let destination_amount_swapped = destination_amount_before_fees.checked_sub(trade_fee)?;
+
+        Some(SwapResult {
+            new_swap_source_amount: swap_source_amount.checked_add(source_amount)?,
+            new_swap_destination_amount: swap_destination_amount
+                .checked_sub(destination_amount_swapped)?,
+            source_amount_swapped: source_amount,
+            destination_amount_swapped,
+            trade_fee,
+            protocol_fee,
+            fund_fee,
+        })
+    }
+
+    /// 计算得到给定数量的目的地代币需要多少源代币，
+    /// 费用从目的地代币中扣除。
+    pub fn swap_base_output_with_output_fee(
+        destinsation_amount: u128,
+        swap_source_amount: u128,
+        swap_destination_amount: u128,
+        trade_fee_rate: u64,
+        protocol_fee_rate: u64,
+        fund_fee_rate: u64,
+    ) -> Option<SwapResult> {
+        let destination_amount_before_fees =
+            Fees::calculate_pre_fee_amount(destinsation_amount, trade_fee_rate)?;
+        if destination_amount_before_fees >= swap_destination_amount {
+            return None;
+        }
+        let source_amount_swapped = ConstantProductCurve::swap_base_output_without_fees(
+            destination_amount_before_fees,
+            swap_source_amount,
+            swap_destination_amount,
+        );
+
+        let trade_fee = destination_amount_before_fees.checked_sub(destinsation_amount)?;
+        let protocol_fee = Fees::protocol_fee(trade_fee, protocol_fee_rate)?;
+        let fund_fee = Fees::fund_fee(trade_fee, fund_fee_rate)?;
+
+        Some(SwapResult {
+            new_swap_source_amount: swap_source_amount.checked_add(source_amount_swapped)?,
+            new_swap_destination_amount: swap_destination_amount
+                .checked_sub(destinsation_amount)?,
+            source_amount_swapped,
+            destination_amount_swapped: destinsation_amount,
+            trade_fee,
+            protocol_fee,
+            fund_fee,
+        })
+    }
+
     /// 获取给定数量的池代币的交易代币数量，
     /// 提供总交易代币和矿池代币供应量。
     pub fn lp_tokens_to_trading_tokens(
@@ -172,6 +243,24 @@ impl CurveCalculator {
             round_direction,
         )
     }
+
+    /// 获取给定数量的交易代币按池比例最多可以存入的池代币数量，
+    /// 存入所需的交易代币向上取整后不超过给定数量。
+    pub fn trading_tokens_to_max_lp_tokens(
+        token_0_amount: u128,
+        token_1_amount: u128,
+        lp_token_supply: u128,
+        swap_token_0_amount: u128,
+        swap_token_1_amount: u128,
+    ) -> Option<u128> {
+        let lp_token_0_amount = token_0_amount
+            .checked_mul(lp_token_supply)?
+            .checked_div(swap_token_0_amount)?;
+        let lp_token_1_amount = token_1_amount
+            .checked_mul(lp_token_supply)?
+            .checked_div(swap_token_1_amount)?;
+        Some(lp_token_0_amount.min(lp_token_1_amount))
+    }
 }
 
 /// 曲线测试助手
@@ -336,6 +425,94 @@ pub mod test {
             .greater_than_or_equal(&value.checked_mul(&new_lp_token_supply).unwrap()));
     }
 
+    #[test]
+    fn trading_tokens_to_max_lp_tokens() {
+        let (lp_token_supply, swap_token_0_amount, swap_token_1_amount) = (1000, 3001, 7919);
+        for (token_0_amount, token_1_amount) in [(0, 100), (30, 1), (31, 80), (100, 500), (7, 7)] {
+            let lp_token_amount = CurveCalculator::trading_tokens_to_max_lp_tokens(
+                token_0_amount,
+                token_1_amount,
+                lp_token_supply,
+                swap_token_0_amount,
+                swap_token_1_amount,
+            )
+            .unwrap();
+            let results = CurveCalculator::lp_tokens_to_trading_tokens(
+                lp_token_amount,
+                lp_token_supply,
+                swap_token_0_amount,
+                swap_token_1_amount,
+                RoundDirection::Ceiling,
+            )
+            .unwrap();
+            assert!(results.token_0_amount <= token_0_amount);
+            assert!(results.token_1_amount <= token_1_amount);
+            // one more lp token needs more than the given amounts
+            let results = CurveCalculator::lp_tokens_to_trading_tokens(
+                lp_token_amount + 1,
+                lp_token_supply,
+                swap_token_0_amount,
+                swap_token_1_amount,
+                RoundDirection::Ceiling,
+            )
+            .unwrap();
+            assert!(
+                results.token_0_amount > token_0_amount || results.token_1_amount > token_1_amount
+            );
+        }
+    }
+
+    #[test]
+    fn swap_with_output_fee() {
+        let (swap_source_amount, swap_destination_amount) = (1_000_000u128, 2_000_000u128);
+        let result = CurveCalculator::swap_base_input_with_output_fee(
+            1000,
+            swap_source_amount,
+            swap_destination_amount,
+            2500,
+            120_000,
+            40_000,
+        )
+        .unwrap();
+        // 1998 before fees, the fee is rounded up
+        assert_eq!(result.destination_amount_swapped, 1993);
+        assert_eq!(result.trade_fee, 5);
+        assert_eq!(result.protocol_fee, 0);
+        assert_eq!(result.new_swap_source_amount, 1_001_000);
+        assert_eq!(result.new_swap_destination_amount, 1_998_007);
+        assert!(
+            result.new_swap_source_amount * (result.new_swap_destination_amount - result.trade_fee)
+                >= swap_source_amount * swap_destination_amount
+        );
+
+        let result = CurveCalculator::swap_base_output_with_output_fee(
+            1993,
+            swap_source_amount,
+            swap_destination_amount,
+            2500,
+            120_000,
+            40_000,
+        )
+        .unwrap();
+        assert_eq!(result.destination_amount_swapped, 1993);
+        assert_eq!(result.trade_fee, 5);
+        assert!(result.source_amount_swapped <= 1000);
+        assert!(
+            result.new_swap_source_amount * (result.new_swap_destination_amount - result.trade_fee)
+                >= swap_source_amount * swap_destination_amount
+        );
+
+        assert!(CurveCalculator::swap_base_output_with_output_fee(
+            swap_destination_amount,
+            swap_source_amount,
+            swap_destination_amount,
+            2500,
+            120_000,
+            40_000,
+        )
+        .is_none());
+    }
+
     prop_compose! {
         pub fn total_and_intermediate(max_value: u64)(total in 1..max_value)
                         (intermediate in 1..total, total in Just(total))
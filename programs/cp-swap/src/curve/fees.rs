@@ -1,6 +1,8 @@
 //！所有费用信息，当前用于验证
 
 pub const FEE_RATE_DENOMINATOR_VALUE: u64 = 1_000_000;
+/// Number of times the extra launch fee halves over the launch duration in exponential mode
+pub const LAUNCH_FEE_HALVINGS: u64 = 10;
 
 pub struct Fees {}
 
@@ -50,6 +52,15 @@ impl Fees {
         )
     }
 
+    /// Calculate the referral fee taken from the protocol fee
+    pub fn referral_fee(protocol_fee: u128, referral_fee_rate: u64) -> Option<u128> {
+        floor_div(
+            protocol_fee,
+            u128::from(referral_fee_rate),
+            u128::from(FEE_RATE_DENOMINATOR_VALUE),
+        )
+    }
+
     pub fn calculate_pre_fee_amount(post_fee_amount: u128, trade_fee_rate: u64) -> Option<u128> {
         if trade_fee_rate == 0 {
             Some(post_fee_amount)
@@ -64,4 +75,100 @@ impl Fees {
                 .checked_div(denominator)
         }
     }
+
+    /// Calculate the fee rate decaying from `start_fee_rate` to `end_fee_rate` over `duration`
+    /// seconds, linearly or halving `LAUNCH_FEE_HALVINGS` times exponentially
+    pub fn decayed_fee_rate(
+        start_fee_rate: u64,
+        end_fee_rate: u64,
+        elapsed: u64,
+        duration: u64,
+        exponential: bool,
+    ) -> u64 {
+        if start_fee_rate <= end_fee_rate || elapsed >= duration {
+            return end_fee_rate;
+        }
+        let extra_fee_rate = u128::from(start_fee_rate - end_fee_rate);
+        let (elapsed, duration) = (u128::from(elapsed), u128::from(duration));
+        let remaining_fee_rate = if exponential {
+            let halving_progress = elapsed * u128::from(LAUNCH_FEE_HALVINGS);
+            let current = extra_fee_rate >> (halving_progress / duration);
+            let next = current >> 1;
+            // interpolate linearly inside the current halving period
+            current - (current - next) * (halving_progress % duration) / duration
+        } else {
+            extra_fee_rate * (duration - elapsed) / duration
+        };
+        end_fee_rate + remaining_fee_rate as u64
+    }
+}
+
+#[cfg(test)]
+mod fees_test {
+    use super::*;
+
+    #[test]
+    fn referral_fee_test() {
+        // 20% of the protocol fee
+        assert_eq!(Fees::referral_fee(1000, 200_000), Some(200));
+        assert_eq!(Fees::referral_fee(999, 200_000), Some(199));
+        assert_eq!(Fees::referral_fee(1000, 0), Some(0));
+        assert_eq!(
+            Fees::referral_fee(1000, FEE_RATE_DENOMINATOR_VALUE),
+            Some(1000)
+        );
+    }
+
+    #[test]
+    fn decayed_fee_rate_linear_test() {
+        assert_eq!(
+            Fees::decayed_fee_rate(100_000, 2500, 0, 100, false),
+            100_000
+        );
+        assert_eq!(
+            Fees::decayed_fee_rate(100_000, 2500, 50, 100, false),
+            51_250
+        );
+        assert_eq!(Fees::decayed_fee_rate(100_000, 2500, 99, 100, false), 3475);
+        assert_eq!(Fees::decayed_fee_rate(100_000, 2500, 100, 100, false), 2500);
+        assert_eq!(
+            Fees::decayed_fee_rate(100_000, 2500, 1000, 100, false),
+            2500
+        );
+        // start fee is lower than the trade fee, nothing to decay
+        assert_eq!(Fees::decayed_fee_rate(1000, 2500, 0, 100, false), 2500);
+    }
+
+    #[test]
+    fn decayed_fee_rate_exponential_test() {
+        let extra = 102_400;
+        assert_eq!(
+            Fees::decayed_fee_rate(2500 + extra, 2500, 0, 100, true),
+            2500 + extra
+        );
+        // one halving every 10 seconds
+        assert_eq!(
+            Fees::decayed_fee_rate(2500 + extra, 2500, 10, 100, true),
+            2500 + extra / 2
+        );
+        assert_eq!(
+            Fees::decayed_fee_rate(2500 + extra, 2500, 15, 100, true),
+            2500 + extra * 3 / 8
+        );
+        assert_eq!(
+            Fees::decayed_fee_rate(2500 + extra, 2500, 90, 100, true),
+            2500 + extra / 512
+        );
+        assert_eq!(
+            Fees::decayed_fee_rate(2500 + extra, 2500, 100, 100, true),
+            2500
+        );
+
+        let mut last = u64::MAX;
+        for elapsed in 0..=100 {
+            let fee_rate = Fees::decayed_fee_rate(2500 + extra, 2500, elapsed, 100, true);
+            assert!(fee_rate <= last && fee_rate >= 2500);
+            last = fee_rate;
+        }
+    }
 }
@@ -28,8 +28,54 @@ pub enum ErrorCode {
     NotSupportMint,
     #[msg("invaild vault")]
     InvalidVault,
-    #[msg("Init lp amount is too less(Because 100 amount lp will be locked)")]
+    #[msg("Init lp amount is too less(Because the lock lp amount of config will be locked)")]
     InitLpAmountTooLess,
     #[msg("TransferFee calculate not match")]
     TransferFeeCalculateNotMatch,
+    #[msg("Mint policy list is full")]
+    MintPolicyListFull,
+    #[msg("Swap amount exceeds the max trade size of the launch window")]
+    ExceededMaxTradeSize,
+    #[msg("Pre-open allow list is full")]
+    AllowListFull,
+    #[msg("Observation update duration has not passed")]
+    ObservationNotDue,
+    #[msg("Price after the swap deviates too far from the twap")]
+    ExceededTwapDeviation,
+    #[msg("Price guard or reference price account is missing")]
+    MissingPriceGuardAccount,
+    #[msg("Invalid reference price")]
+    InvalidReferencePrice,
+    #[msg("Reference price is stale")]
+    StaleReferencePrice,
+    #[msg("Price after the swap deviates too far from the reference price")]
+    ExceededReferencePriceDeviation,
+    #[msg("Price impact of the swap exceeds the max price impact")]
+    ExceededMaxPriceImpact,
+    #[msg("Pool hook or hook program account is missing")]
+    MissingPoolHookAccount,
+    #[msg("Outflow of the vault exceeds the rate limit")]
+    ExceededOutflowLimit,
+    #[msg("Mint policy account is missing")]
+    MissingMintPolicyAccount,
+    #[msg("Withdraw exceeds the unlocked liquidity")]
+    ExceededLockedLiquidity,
+    #[msg("Invalid referrer token account")]
+    InvalidReferrer,
+    #[msg("Direction fee rate exceeds the max direction fee rate of config")]
+    ExceededMaxDirectionFeeRate,
+    #[msg("Fund fee must be collected by the fee split")]
+    FeeSplitEnabled,
+    #[msg("Observation account is already extended")]
+    ObservationAlreadyExtended,
+    #[msg("Swaps are paused by the circuit breaker")]
+    CircuitBreakerPaused,
+    #[msg("Missing the price feed account of the pool")]
+    MissingPriceFeedAccount,
+    #[msg("Missing the circuit breaker account of the pool")]
+    MissingCircuitBreakerAccount,
+    #[msg("Pool hook program is not executable")]
+    InvalidPoolHookProgram,
+    #[msg("Fee discount tokens are still locked")]
+    FeeDiscountLocked,
 }
@@ -9,7 +9,7 @@ use anchor_spl::token_interface::TokenAccount;
 #[derive(Accounts)]
 pub struct CollectFundFee<'info> {
     /// Only admin or fund_owner can collect fee now
-    #[account(constraint = (owner.key() == amm_config.fund_owner || owner.key() == crate::admin::id()) @ ErrorCode::InvalidOwner)]
+    #[account(constraint = (owner.key() == amm_config.fund_owner || owner.key() == crate::admin::ID) @ ErrorCode::InvalidOwner)]
     pub owner: Signer<'info>,
 
     /// 检查：金库和 lp 铸币机构
@@ -29,6 +29,17 @@ pub struct CollectFundFee<'info> {
     #[account(address = pool_state.load()?.amm_config)]
     pub amm_config: Account<'info, AmmConfig>,
 
+    /// 配置的资金费用分配，存在时资金费用只能通过 collect_fund_fee_split 收取
+    #[account(
+        seeds = [
+            FEE_SPLIT_SEED.as_bytes(),
+            amm_config.key().as_ref(),
+        ],
+        bump,
+        constraint = fee_split.owner != &crate::id() @ ErrorCode::FeeSplitEnabled
+    )]
+    pub fee_split: UncheckedAccount<'info>,
+
     /// 持有 token_0 池代币的地址
     #[account(
         mut,
@@ -70,8 +81,8 @@ pub struct CollectFundFee<'info> {
     pub token_program_2022: Program<'info, Token2022>,
 }
 
-pub fn collect_fund_fee(
-    ctx: Context<CollectFundFee>,
+pub fn collect_fund_fee<'info>(
+    ctx: Context<'_, '_, '_, 'info, CollectFundFee<'info>>,
     amount_0_requested: u64,
     amount_1_requested: u64,
 ) -> Result<()> {
@@ -98,6 +109,7 @@ pub fn collect_fund_fee(
         } else {
             ctx.accounts.token_program_2022.to_account_info()
         },
+        ctx.remaining_accounts,
         amount_0,
         ctx.accounts.vault_0_mint.decimals,
         &[&[crate::AUTH_SEED.as_bytes(), &[auth_bump]]],
@@ -113,6 +125,7 @@ pub fn collect_fund_fee(
         } else {
             ctx.accounts.token_program_2022.to_account_info()
         },
+        ctx.remaining_accounts,
         amount_1,
         ctx.accounts.vault_1_mint.decimals,
         &[&[crate::AUTH_SEED.as_bytes(), &[auth_bump]]],
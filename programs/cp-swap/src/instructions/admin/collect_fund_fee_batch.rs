@@ -0,0 +1,62 @@
+use super::collect_protocol_fee_batch::collect_fees_from_pools;
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::Token2022;
+
+#[derive(Accounts)]
+pub struct CollectFundFeeBatch<'info> {
+    /// Only admin or fund_owner can collect fee now
+    #[account(constraint = (owner.key() == amm_config.fund_owner || owner.key() == crate::admin::ID) @ ErrorCode::InvalidOwner)]
+    pub owner: Signer<'info>,
+
+    /// 检查：金库和 lp 铸币机构
+    #[account(
+        seeds = [
+            crate::AUTH_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    /// Amm 配置帐户存储fund_owner，所有池必须属于此配置
+    pub amm_config: Account<'info, AmmConfig>,
+
+    /// 配置的资金费用分配，存在时资金费用只能通过 collect_fund_fee_split 收取
+    #[account(
+        seeds = [
+            FEE_SPLIT_SEED.as_bytes(),
+            amm_config.key().as_ref(),
+        ],
+        bump,
+        constraint = fee_split.owner != &crate::id() @ ErrorCode::FeeSplitEnabled
+    )]
+    pub fee_split: UncheckedAccount<'info>,
+
+    /// 执行代币传输的 SPL 程序
+    pub token_program: Program<'info, Token>,
+
+    /// SPL 计划 2022 执行代币转账
+    pub token_program_2022: Program<'info, Token2022>,
+}
+
+pub fn collect_fund_fee_batch<'info>(
+    ctx: Context<'_, '_, '_, 'info, CollectFundFeeBatch<'info>>,
+    pool_count: u8,
+) -> Result<()> {
+    collect_fees_from_pools(
+        ctx.accounts.authority.as_ref(),
+        &ctx.accounts.amm_config.key(),
+        ctx.accounts.token_program.as_ref(),
+        ctx.accounts.token_program_2022.as_ref(),
+        ctx.remaining_accounts,
+        pool_count,
+        |pool_state| {
+            let amounts = (pool_state.fund_fees_token_0, pool_state.fund_fees_token_1);
+            pool_state.fund_fees_token_0 = 0;
+            pool_state.fund_fees_token_1 = 0;
+            amounts
+        },
+    )
+}
@@ -0,0 +1,139 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::utils::token::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::Mint;
+use anchor_spl::token_interface::Token2022;
+use anchor_spl::token_interface::TokenAccount;
+use spl_token_2022::extension::StateWithExtensions;
+
+#[derive(Accounts)]
+pub struct CollectFundFeeSplit<'info> {
+    /// Only admin or fund_owner can collect fee now
+    #[account(constraint = (owner.key() == amm_config.fund_owner || owner.key() == crate::admin::ID) @ ErrorCode::InvalidOwner)]
+    pub owner: Signer<'info>,
+
+    /// 检查：金库和 lp 铸币机构
+    #[account(
+        seeds = [
+            crate::AUTH_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    /// 池状态存储累计资金费用金额
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// Amm 配置帐户存储fund_owner
+    #[account(address = pool_state.load()?.amm_config)]
+    pub amm_config: Account<'info, AmmConfig>,
+
+    /// 配置的资金费用分配，存储接收人及权重
+    #[account(
+        seeds = [
+            FEE_SPLIT_SEED.as_bytes(),
+            amm_config.key().as_ref(),
+        ],
+        bump = fee_split.bump,
+    )]
+    pub fee_split: Account<'info, FeeSplit>,
+
+    /// 持有 token_0 池代币的地址
+    #[account(
+        mut,
+        constraint = token_0_vault.key() == pool_state.load()?.token_0_vault
+    )]
+    pub token_0_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 保存 token_1 池代币的地址
+    #[account(
+        mut,
+        constraint = token_1_vault.key() == pool_state.load()?.token_1_vault
+    )]
+    pub token_1_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// token_0金库的铸币厂
+    #[account(
+        address = token_0_vault.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// token_1金库的铸币厂
+    #[account(
+        address = token_1_vault.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 执行代币传输的 SPL 程序
+    pub token_program: Program<'info, Token>,
+
+    /// SPL 计划 2022 执行代币转账
+    pub token_program_2022: Program<'info, Token2022>,
+}
+
+pub fn collect_fund_fee_split<'info>(
+    ctx: Context<'_, '_, '_, 'info, CollectFundFeeSplit<'info>>,
+) -> Result<()> {
+    let fee_split = &ctx.accounts.fee_split;
+    let amount_0: u64;
+    let amount_1: u64;
+    let auth_bump: u8;
+    {
+        let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+        amount_0 = pool_state.fund_fees_token_0;
+        amount_1 = pool_state.fund_fees_token_1;
+        pool_state.fund_fees_token_0 = 0;
+        pool_state.fund_fees_token_1 = 0;
+        auth_bump = pool_state.auth_bump;
+        pool_state.recent_epoch = Clock::get()?.epoch;
+    }
+
+    let vaults = [&ctx.accounts.token_0_vault, &ctx.accounts.token_1_vault];
+    let mints = [&ctx.accounts.vault_0_mint, &ctx.accounts.vault_1_mint];
+    for (index, amount) in [amount_0, amount_1].into_iter().enumerate() {
+        let (vault, mint) = (vaults[index], mints[index]);
+        let token_program = if mint.to_account_info().owner == ctx.accounts.token_program.key {
+            ctx.accounts.token_program.to_account_info()
+        } else {
+            ctx.accounts.token_program_2022.to_account_info()
+        };
+        let shares = fee_split.split(amount);
+        for (i, share) in shares.into_iter().enumerate() {
+            // 份额为 0 的接收人无需提供代币账户
+            if share == 0 {
+                continue;
+            }
+            let recipient_account = ctx
+                .remaining_accounts
+                .iter()
+                .find(|account_info| {
+                    is_token_account_of(account_info, &fee_split.recipients[i], &mint.key())
+                })
+                .ok_or(ErrorCode::InvalidInput)?;
+            transfer_from_pool_vault_to_user(
+                ctx.accounts.authority.to_account_info(),
+                vault.to_account_info(),
+                recipient_account.clone(),
+                mint.to_account_info(),
+                token_program.clone(),
+                ctx.remaining_accounts,
+                share,
+                mint.decimals,
+                &[&[crate::AUTH_SEED.as_bytes(), &[auth_bump]]],
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn is_token_account_of(account_info: &AccountInfo, owner: &Pubkey, mint: &Pubkey) -> bool {
+    let Ok(data) = account_info.try_borrow_data() else {
+        return false;
+    };
+    StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)
+        .is_ok_and(|account| account.base.owner == *owner && account.base.mint == *mint)
+}
@@ -10,7 +10,7 @@ use anchor_spl::token_interface::TokenAccount;
 #[derive(Accounts)]
 pub struct CollectProtocolFee<'info> {
     /// 现在只有管理员或所有者可以收取费用
-    #[account(constraint = (owner.key() == amm_config.protocol_owner || owner.key() == crate::admin::id()) @ ErrorCode::InvalidOwner)]
+    #[account(constraint = (owner.key() == amm_config.protocol_owner || owner.key() == crate::admin::ID) @ ErrorCode::InvalidOwner)]
     pub owner: Signer<'info>,
 
     /// 检查：金库和 lp 铸币机构
@@ -71,8 +71,8 @@ pub struct CollectProtocolFee<'info> {
     pub token_program_2022: Program<'info, Token2022>,
 }
 
-pub fn collect_protocol_fee(
-    ctx: Context<CollectProtocolFee>,
+pub fn collect_protocol_fee<'info>(
+    ctx: Context<'_, '_, '_, 'info, CollectProtocolFee<'info>>,
     amount_0_requested: u64,
     amount_1_requested: u64,
 ) -> Result<()> {
@@ -107,6 +107,7 @@ pub fn collect_protocol_fee(
         } else {
             ctx.accounts.token_program_2022.to_account_info()
         },
+        ctx.remaining_accounts,
         amount_0,
         ctx.accounts.vault_0_mint.decimals,
         &[&[crate::AUTH_SEED.as_bytes(), &[auth_bump]]],
@@ -122,6 +123,7 @@ pub fn collect_protocol_fee(
         } else {
             ctx.accounts.token_program_2022.to_account_info()
         },
+        ctx.remaining_accounts,
         amount_1,
         ctx.accounts.vault_1_mint.decimals,
         &[&[crate::AUTH_SEED.as_bytes(), &[auth_bump]]],
@@ -0,0 +1,154 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::Token2022;
+use spl_token_2022::extension::StateWithExtensions;
+
+/// Number of accounts of each pool passed by remaining accounts:
+/// pool_state, token_0_vault, token_1_vault, vault_0_mint, vault_1_mint
+pub const COLLECT_FEE_POOL_ACCOUNTS_LEN: usize = 5;
+
+#[derive(Accounts)]
+pub struct CollectProtocolFeeBatch<'info> {
+    /// 现在只有管理员或所有者可以收取费用
+    #[account(constraint = (owner.key() == amm_config.protocol_owner || owner.key() == crate::admin::ID) @ ErrorCode::InvalidOwner)]
+    pub owner: Signer<'info>,
+
+    /// 检查：金库和 lp 铸币机构
+    #[account(
+        seeds = [
+            crate::AUTH_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    /// Amm 配置帐户商店所有者，所有池必须属于此配置
+    pub amm_config: Account<'info, AmmConfig>,
+
+    /// 执行代币传输的 SPL 程序
+    pub token_program: Program<'info, Token>,
+
+    /// SPL 计划 2022 执行代币转账
+    pub token_program_2022: Program<'info, Token2022>,
+}
+
+pub fn collect_protocol_fee_batch<'info>(
+    ctx: Context<'_, '_, '_, 'info, CollectProtocolFeeBatch<'info>>,
+    pool_count: u8,
+) -> Result<()> {
+    collect_fees_from_pools(
+        ctx.accounts.authority.as_ref(),
+        &ctx.accounts.amm_config.key(),
+        ctx.accounts.token_program.as_ref(),
+        ctx.accounts.token_program_2022.as_ref(),
+        ctx.remaining_accounts,
+        pool_count,
+        |pool_state| {
+            let amounts = (
+                pool_state.protocol_fees_token_0,
+                pool_state.protocol_fees_token_1,
+            );
+            pool_state.protocol_fees_token_0 = 0;
+            pool_state.protocol_fees_token_1 = 0;
+            amounts
+        },
+    )
+}
+
+/// Sweep the fees taken by `take_fees` from each pool of the remaining accounts.
+/// The first `pool_count` groups of remaining accounts are the pool accounts, followed by
+/// the recipient token accounts, the fees are sent to the first recipient of the same mint
+pub(crate) fn collect_fees_from_pools<'info>(
+    authority: &AccountInfo<'info>,
+    amm_config: &Pubkey,
+    token_program: &AccountInfo<'info>,
+    token_program_2022: &AccountInfo<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    pool_count: u8,
+    mut take_fees: impl FnMut(&mut PoolState) -> (u64, u64),
+) -> Result<()> {
+    let pool_accounts_len = usize::from(pool_count) * COLLECT_FEE_POOL_ACCOUNTS_LEN;
+    require_gte!(
+        remaining_accounts.len(),
+        pool_accounts_len,
+        ErrorCode::InvalidInput
+    );
+    let (pool_accounts, recipient_accounts) = remaining_accounts.split_at(pool_accounts_len);
+    for accounts in pool_accounts.chunks_exact(COLLECT_FEE_POOL_ACCOUNTS_LEN) {
+        let (pool_info, vaults, mints) = (&accounts[0], &accounts[1..3], &accounts[3..5]);
+        let pool_state_loader = AccountLoad::<PoolState>::try_from(pool_info)?;
+        let (amounts, auth_bump) = {
+            let mut pool_state = pool_state_loader.load_mut()?;
+            require_keys_eq!(pool_state.amm_config, *amm_config, ErrorCode::InvalidInput);
+            require_keys_eq!(
+                pool_state.token_0_vault,
+                vaults[0].key(),
+                ErrorCode::InvalidVault
+            );
+            require_keys_eq!(
+                pool_state.token_1_vault,
+                vaults[1].key(),
+                ErrorCode::InvalidVault
+            );
+            require_keys_eq!(
+                pool_state.token_0_mint,
+                mints[0].key(),
+                ErrorCode::InvalidInput
+            );
+            require_keys_eq!(
+                pool_state.token_1_mint,
+                mints[1].key(),
+                ErrorCode::InvalidInput
+            );
+
+            let (amount_0, amount_1) = take_fees(&mut pool_state);
+            if amount_0 != 0 || amount_1 != 0 {
+                pool_state.recent_epoch = Clock::get()?.epoch;
+            }
+            ([amount_0, amount_1], pool_state.auth_bump)
+        };
+
+        for ((vault, mint), amount) in vaults.iter().zip(mints).zip(amounts) {
+            // skip zero balances, no recipient is needed
+            if amount == 0 {
+                continue;
+            }
+            let recipient = recipient_accounts
+                .iter()
+                .find(|account_info| is_token_account_of_mint(account_info, mint.key))
+                .ok_or(ErrorCode::InvalidInput)?;
+            transfer_from_pool_vault_to_user(
+                authority.clone(),
+                vault.clone(),
+                recipient.clone(),
+                mint.clone(),
+                if mint.owner == token_program.key {
+                    token_program.clone()
+                } else {
+                    token_program_2022.clone()
+                },
+                remaining_accounts,
+                amount,
+                get_mint_decimals(mint)?,
+                &[&[crate::AUTH_SEED.as_bytes(), &[auth_bump]]],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn is_token_account_of_mint(account_info: &AccountInfo, mint: &Pubkey) -> bool {
+    if !account_info.is_writable
+        || (*account_info.owner != Token::id() && *account_info.owner != Token2022::id())
+    {
+        return false;
+    }
+    let Ok(data) = account_info.try_borrow_data() else {
+        return false;
+    };
+    StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)
+        .is_ok_and(|account| account.base.mint == *mint)
+}
@@ -0,0 +1,136 @@
+use crate::curve::CurveCalculator;
+use crate::curve::RoundDirection;
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::utils::token::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+#[derive(Accounts)]
+pub struct CompoundProtocolFee<'info> {
+    /// 现在只有管理员或所有者可以复投协议费用
+    #[account(constraint = (owner.key() == amm_config.protocol_owner || owner.key() == crate::admin::ID) @ ErrorCode::InvalidOwner)]
+    pub owner: Signer<'info>,
+
+    /// 检查：金库和 lp 铸币机构
+    #[account(
+        seeds = [
+            crate::AUTH_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    /// 池状态存储累计协议费用金额
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// Amm 配置帐户商店所有者
+    #[account(address = pool_state.load()?.amm_config)]
+    pub amm_config: Account<'info, AmmConfig>,
+
+    /// 持有 token_0 池代币的地址
+    #[account(
+        constraint = token_0_vault.key() == pool_state.load()?.token_0_vault
+    )]
+    pub token_0_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 保存 token_1 池代币的地址
+    #[account(
+        constraint = token_1_vault.key() == pool_state.load()?.token_1_vault
+    )]
+    pub token_1_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Lp代币铸造
+    #[account(
+        mut,
+        address = pool_state.load()?.lp_mint @ ErrorCode::IncorrectLpMint)
+    ]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 协议所有者的 lp 代币帐户，接收复投铸造的 lp 代币
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = amm_config.protocol_owner
+    )]
+    pub treasury_lp_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 代币计划
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn compound_protocol_fee(ctx: Context<CompoundProtocolFee>) -> Result<()> {
+    let pool_id = ctx.accounts.pool_state.key();
+    let pool_state = &mut ctx.accounts.pool_state.load_mut()?;
+    if !pool_state.get_status_by_bit(PoolStatusBitIndex::Deposit) {
+        return err!(ErrorCode::NotApproved);
+    }
+    let (total_token_0_amount, total_token_1_amount) = pool_state.vault_amount_without_fee(
+        ctx.accounts.token_0_vault.amount,
+        ctx.accounts.token_1_vault.amount,
+    );
+    // 按池比例存入协议费用，不平衡的剩余部分保留为协议费用
+    let lp_token_amount = CurveCalculator::trading_tokens_to_max_lp_tokens(
+        u128::from(pool_state.protocol_fees_token_0),
+        u128::from(pool_state.protocol_fees_token_1),
+        u128::from(pool_state.lp_supply),
+        u128::from(total_token_0_amount),
+        u128::from(total_token_1_amount),
+    )
+    .ok_or(ErrorCode::ZeroTradingTokens)?;
+    let results = CurveCalculator::lp_tokens_to_trading_tokens(
+        lp_token_amount,
+        u128::from(pool_state.lp_supply),
+        u128::from(total_token_0_amount),
+        u128::from(total_token_1_amount),
+        RoundDirection::Ceiling,
+    )
+    .ok_or(ErrorCode::ZeroTradingTokens)?;
+    if results.token_0_amount == 0 || results.token_1_amount == 0 {
+        return err!(ErrorCode::ZeroTradingTokens);
+    }
+    let lp_token_amount = u64::try_from(lp_token_amount).unwrap();
+    let token_0_amount = u64::try_from(results.token_0_amount).unwrap();
+    let token_1_amount = u64::try_from(results.token_1_amount).unwrap();
+
+    emit!(LpChangeEvent {
+        pool_id,
+        lp_amount_before: pool_state.lp_supply,
+        token_0_vault_before: total_token_0_amount,
+        token_1_vault_before: total_token_1_amount,
+        token_0_amount,
+        token_1_amount,
+        token_0_transfer_fee: 0,
+        token_1_transfer_fee: 0,
+        change_type: 0
+    });
+
+    // 协议费用已在金库中，从协议费用转为池的流动性
+    pool_state.protocol_fees_token_0 = pool_state
+        .protocol_fees_token_0
+        .checked_sub(token_0_amount)
+        .unwrap();
+    pool_state.protocol_fees_token_1 = pool_state
+        .protocol_fees_token_1
+        .checked_sub(token_1_amount)
+        .unwrap();
+    pool_state.lp_supply = pool_state.lp_supply.checked_add(lp_token_amount).unwrap();
+    pool_state.set_reserves(
+        total_token_0_amount.checked_add(token_0_amount).unwrap(),
+        total_token_1_amount.checked_add(token_1_amount).unwrap(),
+    );
+
+    token_mint_to(
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.lp_mint.to_account_info(),
+        ctx.accounts.treasury_lp_token.to_account_info(),
+        lp_token_amount,
+        &[&[crate::AUTH_SEED.as_bytes(), &[pool_state.auth_bump]]],
+    )?;
+    pool_state.recent_epoch = Clock::get()?.epoch;
+
+    Ok(())
+}
@@ -47,5 +47,7 @@ pub fn create_amm_config(
     amm_config.fund_fee_rate = fund_fee_rate;
     amm_config.create_pool_fee = create_pool_fee;
     amm_config.fund_owner = ctx.accounts.owner.key();
+    amm_config.lock_lp_amount = DEFAULT_LOCK_LP_AMOUNT;
+    amm_config.lp_mint_decimals = DEFAULT_LP_MINT_DECIMALS;
     Ok(())
 }
@@ -0,0 +1,53 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use std::ops::DerefMut;
+
+#[derive(Accounts)]
+pub struct CreateFeeDiscount<'info> {
+    /// 只有管理员可以创建交易费折扣
+    #[account(
+        mut,
+        address = crate::admin::ID @ ErrorCode::InvalidOwner
+    )]
+    pub owner: Signer<'info>,
+
+    /// 交易费折扣所属的配置
+    pub amm_config: Account<'info, AmmConfig>,
+
+    /// 交换者锁定以获得折扣的代币
+    pub discount_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 初始化交易费折扣帐户，存储折扣等级
+    #[account(
+        init,
+        seeds = [
+            FEE_DISCOUNT_SEED.as_bytes(),
+            amm_config.key().as_ref(),
+        ],
+        bump,
+        payer = owner,
+        space = FeeDiscount::LEN
+    )]
+    pub fee_discount: Account<'info, FeeDiscount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_fee_discount(
+    ctx: Context<CreateFeeDiscount>,
+    min_balances: Vec<u64>,
+    discount_bps: Vec<u16>,
+    min_lock_duration: u64,
+) -> Result<()> {
+    let fee_discount = ctx.accounts.fee_discount.deref_mut();
+    fee_discount.bump = ctx.bumps.fee_discount;
+    fee_discount.amm_config = ctx.accounts.amm_config.key();
+    fee_discount.set_tiers(
+        ctx.accounts.discount_mint.key(),
+        &min_balances,
+        &discount_bps,
+        min_lock_duration,
+    )
+}
@@ -0,0 +1,44 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use std::ops::DerefMut;
+
+#[derive(Accounts)]
+pub struct CreateFeeSplit<'info> {
+    /// 只有管理员可以创建资金费用分配
+    #[account(
+        mut,
+        address = crate::admin::ID @ ErrorCode::InvalidOwner
+    )]
+    pub owner: Signer<'info>,
+
+    /// 资金费用分配所属的配置
+    pub amm_config: Account<'info, AmmConfig>,
+
+    /// 初始化资金费用分配帐户，存储接收人及权重
+    #[account(
+        init,
+        seeds = [
+            FEE_SPLIT_SEED.as_bytes(),
+            amm_config.key().as_ref(),
+        ],
+        bump,
+        payer = owner,
+        space = FeeSplit::LEN
+    )]
+    pub fee_split: Account<'info, FeeSplit>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_fee_split(
+    ctx: Context<CreateFeeSplit>,
+    weights: Vec<u16>,
+    dust_recipient_index: u8,
+) -> Result<()> {
+    let recipients: Vec<Pubkey> = ctx.remaining_accounts.iter().map(|a| *a.key).collect();
+    let fee_split = ctx.accounts.fee_split.deref_mut();
+    fee_split.bump = ctx.bumps.fee_split;
+    fee_split.amm_config = ctx.accounts.amm_config.key();
+    fee_split.set_recipients(&recipients, &weights, dust_recipient_index)
+}
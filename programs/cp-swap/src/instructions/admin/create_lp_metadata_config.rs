@@ -0,0 +1,37 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use std::ops::DerefMut;
+
+#[derive(Accounts)]
+pub struct CreateLpMetadataConfig<'info> {
+    /// 只有管理员可以创建 LP 元数据配置
+    #[account(
+        mut,
+        address = crate::admin::ID @ ErrorCode::InvalidOwner
+    )]
+    pub owner: Signer<'info>,
+
+    /// 初始化 LP 元数据配置帐户，存储 LP 铸币元数据的 uri 模板
+    #[account(
+        init,
+        seeds = [
+            LP_METADATA_CONFIG_SEED.as_bytes(),
+        ],
+        bump,
+        payer = owner,
+        space = LpMetadataConfig::LEN
+    )]
+    pub lp_metadata_config: Account<'info, LpMetadataConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_lp_metadata_config(
+    ctx: Context<CreateLpMetadataConfig>,
+    uri_template: String,
+) -> Result<()> {
+    let lp_metadata_config = ctx.accounts.lp_metadata_config.deref_mut();
+    lp_metadata_config.bump = ctx.bumps.lp_metadata_config;
+    lp_metadata_config.set_uri_template(uri_template)
+}
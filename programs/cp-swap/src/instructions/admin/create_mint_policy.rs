@@ -0,0 +1,34 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreateMintPolicy<'info> {
+    /// 只有管理员可以创建铸币策略
+    #[account(
+        mut,
+        address = crate::admin::ID @ ErrorCode::InvalidOwner
+    )]
+    pub owner: Signer<'info>,
+
+    /// 初始化铸币策略帐户，存储允许的扩展和铸币名单
+    #[account(
+        init,
+        seeds = [
+            MINT_POLICY_SEED.as_bytes(),
+        ],
+        bump,
+        payer = owner,
+        space = MintPolicy::LEN
+    )]
+    pub mint_policy: AccountLoader<'info, MintPolicy>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_mint_policy(ctx: Context<CreateMintPolicy>, allowed_extensions: u64) -> Result<()> {
+    let mut mint_policy = ctx.accounts.mint_policy.load_init()?;
+    mint_policy.bump = ctx.bumps.mint_policy;
+    mint_policy.allowed_extensions = allowed_extensions;
+    Ok(())
+}
@@ -7,8 +7,53 @@ pub use update_config::*;
 pub mod update_pool_status;
 pub use update_pool_status::*;
 
+pub mod update_outflow_limit;
+pub use update_outflow_limit::*;
+
 pub mod collect_protocol_fee;
 pub use collect_protocol_fee::*;
 
 pub mod collect_fund_fee;
 pub use collect_fund_fee::*;
+
+pub mod compound_protocol_fee;
+pub use compound_protocol_fee::*;
+
+pub mod collect_protocol_fee_batch;
+pub use collect_protocol_fee_batch::*;
+
+pub mod collect_fund_fee_batch;
+pub use collect_fund_fee_batch::*;
+
+pub mod create_mint_policy;
+pub use create_mint_policy::*;
+
+pub mod update_mint_policy;
+pub use update_mint_policy::*;
+
+pub mod create_lp_metadata_config;
+pub use create_lp_metadata_config::*;
+
+pub mod update_lp_metadata_config;
+pub use update_lp_metadata_config::*;
+
+pub mod create_fee_split;
+pub use create_fee_split::*;
+
+pub mod update_fee_split;
+pub use update_fee_split::*;
+
+pub mod collect_fund_fee_split;
+pub use collect_fund_fee_split::*;
+
+pub mod create_fee_discount;
+pub use create_fee_discount::*;
+
+pub mod update_fee_discount;
+pub use update_fee_discount::*;
+
+pub mod sync;
+pub use sync::*;
+
+pub mod update_price_guard;
+pub use update_price_guard::*;
@@ -0,0 +1,41 @@
+use crate::curve::CurveCalculator;
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+#[derive(Accounts)]
+pub struct SyncReserves<'info> {
+    /// 只有管理员可以同步储备
+    #[account(address = crate::admin::ID @ ErrorCode::InvalidOwner)]
+    pub owner: Signer<'info>,
+
+    /// 池状态存储记账储备
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// 持有 token_0 池代币的地址
+    #[account(
+        constraint = token_0_vault.key() == pool_state.load()?.token_0_vault @ ErrorCode::InvalidVault
+    )]
+    pub token_0_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 保存 token_1 池代币的地址
+    #[account(
+        constraint = token_1_vault.key() == pool_state.load()?.token_1_vault @ ErrorCode::InvalidVault
+    )]
+    pub token_1_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+}
+
+pub fn sync(ctx: Context<SyncReserves>) -> Result<()> {
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    // 将金库的盈余计入储备
+    let (reserve_0, reserve_1) = pool_state.vault_balance_without_fee(
+        ctx.accounts.token_0_vault.amount,
+        ctx.accounts.token_1_vault.amount,
+    );
+    CurveCalculator::validate_supply(reserve_0, reserve_1)?;
+    pool_state.set_reserves(reserve_0, reserve_1);
+    pool_state.recent_epoch = Clock::get()?.epoch;
+    Ok(())
+}
@@ -31,6 +31,11 @@ pub fn update_amm_config(ctx: Context<UpdateAmmConfig>, param: u8, value: u64) -
         }
         Some(5) => amm_config.create_pool_fee = value,
         Some(6) => amm_config.disable_create_pool = if value == 0 { false } else { true },
+        Some(7) => update_lock_lp_amount(amm_config, value)?,
+        Some(8) => update_lp_mint_decimals(amm_config, value)?,
+        Some(9) => update_referral_fee_rate(amm_config, value)?,
+        Some(10) => update_max_price_impact_bps(amm_config, value)?,
+        Some(11) => update_max_direction_fee_rate(amm_config, value)?,
         _ => return err!(ErrorCode::InvalidInput),
     }
 
@@ -54,6 +59,65 @@ fn update_fund_fee_rate(amm_config: &mut Account<AmmConfig>, fund_fee_rate: u64)
     amm_config.fund_fee_rate = fund_fee_rate;
 }
 
+fn update_referral_fee_rate(
+    amm_config: &mut Account<AmmConfig>,
+    referral_fee_rate: u64,
+) -> Result<()> {
+    require!(
+        referral_fee_rate <= FEE_RATE_DENOMINATOR_VALUE,
+        ErrorCode::InvalidInput
+    );
+    amm_config.referral_fee_rate = referral_fee_rate;
+    Ok(())
+}
+
+fn update_max_direction_fee_rate(
+    amm_config: &mut Account<AmmConfig>,
+    max_direction_fee_rate: u64,
+) -> Result<()> {
+    require_gt!(
+        FEE_RATE_DENOMINATOR_VALUE,
+        max_direction_fee_rate,
+        ErrorCode::InvalidInput
+    );
+    amm_config.max_direction_fee_rate = max_direction_fee_rate;
+    Ok(())
+}
+
+fn update_max_price_impact_bps(
+    amm_config: &mut Account<AmmConfig>,
+    max_price_impact_bps: u64,
+) -> Result<()> {
+    require_gte!(
+        BPS_DENOMINATOR,
+        max_price_impact_bps,
+        ErrorCode::InvalidInput
+    );
+    amm_config.max_price_impact_bps = max_price_impact_bps;
+    Ok(())
+}
+
+fn update_lock_lp_amount(amm_config: &mut Account<AmmConfig>, lock_lp_amount: u64) -> Result<()> {
+    require_gt!(lock_lp_amount, 0, ErrorCode::InvalidInput);
+    amm_config.lock_lp_amount = lock_lp_amount;
+    Ok(())
+}
+
+fn update_lp_mint_decimals(
+    amm_config: &mut Account<AmmConfig>,
+    lp_mint_decimals: u64,
+) -> Result<()> {
+    let lp_mint_decimals = u8::try_from(lp_mint_decimals).map_err(|_| ErrorCode::InvalidInput)?;
+    require_gt!(lp_mint_decimals, 0, ErrorCode::InvalidInput);
+    require_gte!(
+        MAX_LP_MINT_DECIMALS,
+        lp_mint_decimals,
+        ErrorCode::InvalidInput
+    );
+    amm_config.lp_mint_decimals = lp_mint_decimals;
+    Ok(())
+}
+
 fn set_new_protocol_owner(amm_config: &mut Account<AmmConfig>, new_owner: Pubkey) -> Result<()> {
     require_keys_neq!(new_owner, Pubkey::default());
     #[cfg(feature = "enable-log")]
@@ -0,0 +1,40 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct UpdateFeeDiscount<'info> {
+    /// 只有管理员可以修改交易费折扣
+    #[account(address = crate::admin::ID @ ErrorCode::InvalidOwner)]
+    pub owner: Signer<'info>,
+
+    /// 交换者锁定以获得折扣的代币
+    pub discount_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 要更改的交易费折扣帐户
+    #[account(
+        mut,
+        seeds = [
+            FEE_DISCOUNT_SEED.as_bytes(),
+            fee_discount.amm_config.as_ref(),
+        ],
+        bump = fee_discount.bump,
+    )]
+    pub fee_discount: Account<'info, FeeDiscount>,
+}
+
+pub fn update_fee_discount(
+    ctx: Context<UpdateFeeDiscount>,
+    min_balances: Vec<u64>,
+    discount_bps: Vec<u16>,
+    min_lock_duration: u64,
+) -> Result<()> {
+    let discount_mint = ctx.accounts.discount_mint.key();
+    ctx.accounts.fee_discount.set_tiers(
+        discount_mint,
+        &min_balances,
+        &discount_bps,
+        min_lock_duration,
+    )
+}
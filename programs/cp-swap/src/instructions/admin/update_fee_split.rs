@@ -0,0 +1,32 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateFeeSplit<'info> {
+    /// 只有管理员可以修改资金费用分配
+    #[account(address = crate::admin::ID @ ErrorCode::InvalidOwner)]
+    pub owner: Signer<'info>,
+
+    /// 要更改的资金费用分配帐户
+    #[account(
+        mut,
+        seeds = [
+            FEE_SPLIT_SEED.as_bytes(),
+            fee_split.amm_config.as_ref(),
+        ],
+        bump = fee_split.bump,
+    )]
+    pub fee_split: Account<'info, FeeSplit>,
+}
+
+pub fn update_fee_split(
+    ctx: Context<UpdateFeeSplit>,
+    weights: Vec<u16>,
+    dust_recipient_index: u8,
+) -> Result<()> {
+    let recipients: Vec<Pubkey> = ctx.remaining_accounts.iter().map(|a| *a.key).collect();
+    ctx.accounts
+        .fee_split
+        .set_recipients(&recipients, &weights, dust_recipient_index)
+}
@@ -0,0 +1,29 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateLpMetadataConfig<'info> {
+    /// 只有管理员可以修改 LP 元数据配置
+    #[account(address = crate::admin::ID @ ErrorCode::InvalidOwner)]
+    pub owner: Signer<'info>,
+
+    /// 要更改的 LP 元数据配置帐户
+    #[account(
+        mut,
+        seeds = [
+            LP_METADATA_CONFIG_SEED.as_bytes(),
+        ],
+        bump = lp_metadata_config.bump,
+    )]
+    pub lp_metadata_config: Account<'info, LpMetadataConfig>,
+}
+
+pub fn update_lp_metadata_config(
+    ctx: Context<UpdateLpMetadataConfig>,
+    uri_template: String,
+) -> Result<()> {
+    ctx.accounts
+        .lp_metadata_config
+        .set_uri_template(uri_template)
+}
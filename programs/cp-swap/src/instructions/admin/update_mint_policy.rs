@@ -0,0 +1,63 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateMintPolicy<'info> {
+    /// 只有管理员可以修改铸币策略
+    #[account(address = crate::admin::ID @ ErrorCode::InvalidOwner)]
+    pub owner: Signer<'info>,
+
+    /// 要更改的铸币策略帐户
+    #[account(
+        mut,
+        seeds = [
+            MINT_POLICY_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub mint_policy: AccountLoader<'info, MintPolicy>,
+}
+
+pub fn update_mint_policy(ctx: Context<UpdateMintPolicy>, param: u8, value: u64) -> Result<()> {
+    let mut mint_policy = ctx.accounts.mint_policy.load_mut()?;
+    // value is only used by param 0
+    require!(param == 0 || value == 0, ErrorCode::InvalidInput);
+    let keys = ctx.remaining_accounts.iter().map(|account| *account.key);
+    match param {
+        0 => mint_policy.allowed_extensions = value,
+        1 => {
+            for mint in keys {
+                mint_policy.add_allow_mint(mint)?;
+            }
+        }
+        2 => {
+            for mint in keys {
+                mint_policy.remove_allow_mint(mint)?;
+            }
+        }
+        3 => {
+            for mint in keys {
+                mint_policy.add_deny_mint(mint)?;
+            }
+        }
+        4 => {
+            for mint in keys {
+                mint_policy.remove_deny_mint(mint)?;
+            }
+        }
+        5 => {
+            for program_id in keys {
+                mint_policy.add_hook_program(program_id)?;
+            }
+        }
+        6 => {
+            for program_id in keys {
+                mint_policy.remove_hook_program(program_id)?;
+            }
+        }
+        _ => return err!(ErrorCode::InvalidInput),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateOutflowLimit<'info> {
+    /// 只有管理员可以设置流出限制
+    #[account(
+        address = crate::admin::ID
+    )]
+    pub authority: Signer<'info>,
+
+    /// 要设置流出限制的池
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+}
+
+pub fn update_outflow_limit(
+    ctx: Context<UpdateOutflowLimit>,
+    max_outflow_bps: u16,
+    window: u16,
+) -> Result<()> {
+    require_gte!(
+        BPS_DENOMINATOR,
+        u64::from(max_outflow_bps),
+        ErrorCode::InvalidInput
+    );
+    if max_outflow_bps != 0 {
+        require_gt!(window, 0, ErrorCode::InvalidInput);
+    }
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    pool_state.set_outflow_limit(max_outflow_bps, window);
+    Ok(())
+}
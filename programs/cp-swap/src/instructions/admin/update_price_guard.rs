@@ -0,0 +1,59 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use std::ops::DerefMut;
+
+#[derive(Accounts)]
+pub struct UpdatePriceGuard<'info> {
+    /// 只有管理员可以设置参考价格，支付创建价格保护的费用
+    #[account(
+        mut,
+        address = crate::admin::ID
+    )]
+    pub authority: Signer<'info>,
+
+    /// 要设置价格保护的池
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// 初始化或更新池的价格保护帐户
+    #[account(
+        init_if_needed,
+        seeds = [
+            PRICE_GUARD_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+        ],
+        bump,
+        payer = authority,
+        space = PriceGuard::LEN
+    )]
+    pub price_guard: Account<'info, PriceGuard>,
+
+    /// CHECK: 外部参考价格帐户，布局见 `ReferencePrice`
+    pub reference_price: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn update_price_guard(
+    ctx: Context<UpdatePriceGuard>,
+    max_deviation_bps: u16,
+    max_staleness: u32,
+) -> Result<()> {
+    if max_deviation_bps != 0 {
+        require!(
+            ReferencePrice::parse(&ctx.accounts.reference_price.try_borrow_data()?).is_some(),
+            ErrorCode::InvalidReferencePrice
+        );
+    }
+    let price_guard = ctx.accounts.price_guard.deref_mut();
+    price_guard.bump = ctx.bumps.price_guard;
+    price_guard.pool_id = ctx.accounts.pool_state.key();
+    price_guard.reference_price = ctx.accounts.reference_price.key();
+    price_guard.max_deviation_bps = max_deviation_bps;
+    price_guard.max_staleness = max_staleness;
+
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    pool_state.set_guard_by_bit(PoolGuardBitIndex::Price, max_deviation_bps != 0);
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use std::ops::DerefMut;
+
+#[derive(Accounts)]
+pub struct ClaimPreOpenPass<'info> {
+    /// 在默克尔树中的钱包，支付创建通行证的费用
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// 允许名单所属的池
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// 存储默克尔根的允许名单帐户
+    #[account(address = pool_state.load()?.pre_open_allow_list)]
+    pub allow_list: AccountLoader<'info, PreOpenAllowList>,
+
+    /// 初始化或更新钱包的通行证帐户
+    #[account(
+        init_if_needed,
+        seeds = [
+            PRE_OPEN_PASS_SEED.as_bytes(),
+            allow_list.key().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump,
+        payer = owner,
+        space = PreOpenPass::LEN
+    )]
+    pub pre_open_pass: Account<'info, PreOpenPass>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_pre_open_pass(ctx: Context<ClaimPreOpenPass>, proof: Vec<[u8; 32]>) -> Result<()> {
+    let allow_list = ctx.accounts.allow_list.load()?;
+    if !allow_list.verify_merkle_proof(ctx.accounts.owner.key, &proof) {
+        return err!(ErrorCode::NotApproved);
+    }
+    let pre_open_pass = ctx.accounts.pre_open_pass.deref_mut();
+    pre_open_pass.bump = ctx.bumps.pre_open_pass;
+    pre_open_pass.allow_list = ctx.accounts.allow_list.key();
+    pre_open_pass.owner = ctx.accounts.owner.key();
+    pre_open_pass.merkle_root = allow_list.merkle_root;
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreatePreOpenAllowList<'info> {
+    /// 池创建者或管理员，支付创建允许名单的费用
+    #[account(
+        mut,
+        constraint = authority.key() == pool_state.load()?.pool_creator
+            || authority.key() == crate::admin::ID @ ErrorCode::InvalidOwner
+    )]
+    pub authority: Signer<'info>,
+
+    /// 允许名单所属的池
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// 初始化允许名单帐户，存储开放时间之前可以交换的钱包
+    #[account(
+        init,
+        seeds = [
+            PRE_OPEN_ALLOW_LIST_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+        ],
+        bump,
+        payer = authority,
+        space = PreOpenAllowList::LEN
+    )]
+    pub allow_list: AccountLoader<'info, PreOpenAllowList>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_pre_open_allow_list(ctx: Context<CreatePreOpenAllowList>) -> Result<()> {
+    let mut allow_list = ctx.accounts.allow_list.load_init()?;
+    allow_list.bump = ctx.bumps.allow_list;
+    allow_list.pool_id = ctx.accounts.pool_state.key();
+
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    pool_state.pre_open_allow_list = ctx.accounts.allow_list.key();
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreatePriceFeed<'info> {
+    /// 池创建者或管理员，支付创建价格源的费用
+    #[account(
+        mut,
+        constraint = authority.key() == pool_state.load()?.pool_creator
+            || authority.key() == crate::admin::ID @ ErrorCode::InvalidOwner
+    )]
+    pub authority: Signer<'info>,
+
+    /// 价格源所属的池，创建后交换必须刷新价格源
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// 初始化价格源帐户，存储池的现货价格和时间加权平均价格
+    #[account(
+        init,
+        seeds = [
+            PRICE_FEED_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+        ],
+        bump,
+        payer = authority,
+        space = PriceFeed::LEN
+    )]
+    pub price_feed: Account<'info, PriceFeed>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_price_feed(ctx: Context<CreatePriceFeed>, twap_window: u64) -> Result<()> {
+    // the twap window must be covered by the observations
+    require_gt!(twap_window, 0, ErrorCode::InvalidInput);
+    require_gte!(
+        (OBSERVATION_NUM as u64 - 1) * OBSERVATION_UPDATE_DURATION_DEFAULT,
+        twap_window,
+        ErrorCode::InvalidInput
+    );
+    let price_feed = &mut ctx.accounts.price_feed;
+    price_feed.bump = ctx.bumps.price_feed;
+    price_feed.pool_id = ctx.accounts.pool_state.key();
+    price_feed.exponent = PRICE_FEED_EXPONENT;
+    price_feed.twap_window = twap_window;
+
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    pool_state.set_guard_by_bit(PoolGuardBitIndex::PriceFeed, true);
+    Ok(())
+}
@@ -82,10 +82,14 @@ pub struct Deposit<'info> {
         address = pool_state.load()?.lp_mint @ ErrorCode::IncorrectLpMint)
     ]
     pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 该程序记录了最近的预言机观察结果
+    #[account(mut, address = pool_state.load()?.observation_key)]
+    pub observation_state: AccountLoader<'info, ObservationState>,
 }
 
-pub fn deposit(
-    ctx: Context<Deposit>,
+pub fn deposit<'info>(
+    ctx: Context<'_, '_, '_, 'info, Deposit<'info>>,
     lp_token_amount: u64,
     maximum_token_0_amount: u64,
     maximum_token_1_amount: u64,
@@ -100,6 +104,18 @@ pub fn deposit(
         ctx.accounts.token_0_vault.amount,
         ctx.accounts.token_1_vault.amount,
     );
+    // 将存款前的价格更新为观察值
+    {
+        let (mut observation_state, mut observation_ticks) =
+            oracle::load_observation_mut(&ctx.accounts.observation_state)?;
+        pool_state.observe(
+            &mut observation_state,
+            observation_ticks.as_deref_mut(),
+            ctx.accounts.token_0_vault.amount,
+            ctx.accounts.token_1_vault.amount,
+            oracle::block_timestamp(),
+        );
+    }
     let results = CurveCalculator::lp_tokens_to_trading_tokens(
         u128::from(lp_token_amount),
         u128::from(pool_state.lp_supply),
@@ -171,6 +187,7 @@ pub fn deposit(
         } else {
             ctx.accounts.token_program_2022.to_account_info()
         },
+        ctx.remaining_accounts,
         transfer_token_0_amount,
         ctx.accounts.vault_0_mint.decimals,
     )?;
@@ -185,11 +202,16 @@ pub fn deposit(
         } else {
             ctx.accounts.token_program_2022.to_account_info()
         },
+        ctx.remaining_accounts,
         transfer_token_1_amount,
         ctx.accounts.vault_1_mint.decimals,
     )?;
 
     pool_state.lp_supply = pool_state.lp_supply.checked_add(lp_token_amount).unwrap();
+    pool_state.set_reserves(
+        total_token_0_amount.checked_add(token_0_amount).unwrap(),
+        total_token_1_amount.checked_add(token_1_amount).unwrap(),
+    );
 
     token_mint_to(
         ctx.accounts.authority.to_account_info(),
@@ -201,5 +223,21 @@ pub fn deposit(
     )?;
     pool_state.recent_epoch = Clock::get()?.epoch;
 
+    // 调用池的钩子程序
+    invoke_pool_hook(
+        pool_state,
+        &ctx.accounts.owner.to_account_info(),
+        PoolHookAction {
+            pool_id,
+            user: ctx.accounts.owner.key(),
+            kind: PoolHookActionKind::Deposit as u8,
+            trade_direction: 0,
+            token_0_amount,
+            token_1_amount,
+            lp_amount: lp_token_amount,
+        },
+        ctx.remaining_accounts,
+    )?;
+
     Ok(())
 }
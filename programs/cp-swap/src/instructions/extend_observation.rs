@@ -0,0 +1,48 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+#[derive(Accounts)]
+pub struct ExtendObservation<'info> {
+    /// 任何人都可以支付扩展观察帐户的租金
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// 池状态
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// 扩展后存储累计价格刻度的观察帐户
+    #[account(mut, address = pool_state.load()?.observation_key)]
+    pub observation_state: AccountLoader<'info, ObservationState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn extend_observation(ctx: Context<ExtendObservation>) -> Result<()> {
+    let observation_info = ctx.accounts.observation_state.to_account_info();
+    let new_len = ObservationState::LEN + ObservationTicks::LEN;
+    require_gt!(
+        new_len,
+        observation_info.data_len(),
+        ErrorCode::ObservationAlreadyExtended
+    );
+    // 补足扩展后的租金
+    let rent = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(observation_info.lamports());
+    if rent > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: observation_info.clone(),
+                },
+            ),
+            rent,
+        )?;
+    }
+    observation_info.realloc(new_len, true)?;
+    Ok(())
+}
@@ -10,6 +10,10 @@ use anchor_lang::{
 };
 use anchor_spl::{
     associated_token::AssociatedToken,
+    metadata::{
+        create_metadata_accounts_v3, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3,
+        Metadata,
+    },
     token::spl_token,
     token::Token,
     token_interface::{Mint, TokenAccount, TokenInterface},
@@ -26,6 +30,24 @@ pub struct Initialize<'info> {
     /// 池属于哪个配置。
     pub amm_config: Box<Account<'info, AmmConfig>>,
 
+    /// 铸币策略，决定哪些 token_2022 铸币可以创建池
+    #[account(
+        seeds = [
+            MINT_POLICY_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub mint_policy: AccountLoader<'info, MintPolicy>,
+
+    /// LP 元数据配置，提供 LP 铸币元数据的 uri 模板
+    #[account(
+        seeds = [
+            LP_METADATA_CONFIG_SEED.as_bytes(),
+        ],
+        bump = lp_metadata_config.bump,
+    )]
+    pub lp_metadata_config: Box<Account<'info, LpMetadataConfig>>,
+
     /// 检查：金库和 lp 铸币机构
     #[account(
         seeds = [
@@ -49,7 +71,11 @@ pub struct Initialize<'info> {
     pub pool_state: UncheckedAccount<'info>,
 
     /// Token_0 铸币，密钥必须小于 token_1 铸币。
+    #[account(
         constraint = token_0_mint.key() < token_1_mint.key(),
+        mint::token_program = token_0_program,
+    )]
+    pub token_0_mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// 代币 1 铸造，密钥必须大于代币 0 铸造。
     #[account(
@@ -57,6 +83,12 @@ pub struct Initialize<'info> {
     )]
     pub token_1_mint: Box<InterfaceAccount<'info, Mint>>,
 
+    /// 检查：代币 0 的 metaplex 元数据帐户，用于生成 LP 名称，在 token_2022 元数据扩展中时可省略
+    pub token_0_metadata: Option<UncheckedAccount<'info>>,
+
+    /// 检查：代币 1 的 metaplex 元数据帐户，用于生成 LP 名称，在 token_2022 元数据扩展中时可省略
+    pub token_1_metadata: Option<UncheckedAccount<'info>>,
+
     /// 池 LP 薄荷
     #[account(
         init,
@@ -65,13 +97,26 @@ pub struct Initialize<'info> {
             pool_state.key().as_ref(),
         ],
         bump,
-        mint::decimals = 9,
+        mint::decimals = amm_config.get_lp_mint_decimals(),
         mint::authority = authority,
         payer = creator,
         mint::token_program = token_program,
     )]
     pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
 
+    /// 检查：LP 铸币的 metaplex 元数据帐户，由元数据程序初始化
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            lp_mint.key().as_ref(),
+        ],
+        seeds::program = metadata_program.key(),
+        bump,
+    )]
+    pub lp_mint_metadata: UncheckedAccount<'info>,
+
     /// 付款人token0账户
     #[account(
         mut,
@@ -98,8 +143,11 @@ pub struct Initialize<'info> {
     )]
     pub creator_lp_token: Box<InterfaceAccount<'info, TokenAccount>>,
 
- /// 检查：池的 Token_0 保管库
+    /// 检查：池的 Token_0 保管库
     #[account(
+        mut,
+        seeds = [
+            POOL_VAULT_SEED.as_bytes(),
             pool_state.key().as_ref(),
             token_0_mint.key().as_ref()
         ],
@@ -107,7 +155,7 @@ pub struct Initialize<'info> {
     )]
     pub token_0_vault: UncheckedAccount<'info>,
 
-/// 检查：池的 Token_1 保管库
+    /// 检查：池的 Token_1 保管库
     #[account(
         mut,
         seeds = [
@@ -135,7 +183,7 @@ pub struct Initialize<'info> {
         ],
         bump,
         payer = creator,
-        space = ObservationState::LEN
+        space = ObservationState::LEN + ObservationTicks::LEN
     )]
     pub observation_state: AccountLoader<'info, ObservationState>,
 
@@ -147,22 +195,27 @@ pub struct Initialize<'info> {
     pub token_1_program: Interface<'info, TokenInterface>,
     /// 创建用于接收头寸 NFT 的 ATA 的程序
     pub associated_token_program: Program<'info, AssociatedToken>,
+    /// 创建 LP 铸币元数据的程序
+    pub metadata_program: Program<'info, Metadata>,
     /// 创建新的计划帐户
     pub system_program: Program<'info, System>,
     /// 程序帐户的 Sysvar
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn initialize(
-    ctx: Context<Initialize>,
+pub fn initialize<'info>(
+    ctx: Context<'_, '_, '_, 'info, Initialize<'info>>,
     init_amount_0: u64,
     init_amount_1: u64,
     mut open_time: u64,
 ) -> Result<()> {
-    if !(is_supported_mint(&ctx.accounts.token_0_mint).unwrap()
-        && is_supported_mint(&ctx.accounts.token_1_mint).unwrap())
     {
-        return err!(ErrorCode::NotSupportMint);
+        let mint_policy = ctx.accounts.mint_policy.load()?;
+        if !(is_supported_mint(&ctx.accounts.token_0_mint, &mint_policy)?
+            && is_supported_mint(&ctx.accounts.token_1_mint, &mint_policy)?)
+        {
+            return err!(ErrorCode::NotSupportMint);
+        }
     }
 
     if ctx.accounts.amm_config.disable_create_pool {
@@ -222,6 +275,7 @@ pub fn initialize(
         ctx.accounts.token_0_vault.to_account_info(),
         ctx.accounts.token_0_mint.to_account_info(),
         ctx.accounts.token_0_program.to_account_info(),
+        ctx.remaining_accounts,
         init_amount_0,
         ctx.accounts.token_0_mint.decimals,
     )?;
@@ -232,6 +286,7 @@ pub fn initialize(
         ctx.accounts.token_1_vault.to_account_info(),
         ctx.accounts.token_1_mint.to_account_info(),
         ctx.accounts.token_1_program.to_account_info(),
+        ctx.remaining_accounts,
         init_amount_1,
         ctx.accounts.token_1_mint.decimals,
     )?;
@@ -257,12 +312,11 @@ pub fn initialize(
 
     CurveCalculator::validate_supply(token_0_vault.amount, token_1_vault.amount)?;
 
-    let liquidity = U128::from(token_0_vault.amount)
-        .checked_mul(token_1_vault.amount.into())
-        .unwrap()
-        .integer_sqrt()
-        .as_u64();
-    let lock_lp_amount = 100;
+    // 初始流动性和锁定数量按 LP 铸币的小数位数缩放
+    let (liquidity, lock_lp_amount) = ctx
+        .accounts
+        .amm_config
+        .initial_lp_amounts(token_0_vault.amount, token_1_vault.amount);
     msg!(
         "liquidity:{}, lock_lp_amount:{}, vault_0_amount:{},vault_1_amount:{}",
         liquidity,
@@ -281,6 +335,8 @@ pub fn initialize(
         &[&[crate::AUTH_SEED.as_bytes(), &[ctx.bumps.authority]]],
     )?;
 
+    create_lp_mint_metadata(&ctx)?;
+
     // Charge the fee to create a pool
     if ctx.accounts.amm_config.create_pool_fee != 0 {
         invoke(
@@ -310,6 +366,7 @@ pub fn initialize(
     pool_state.initialize(
         ctx.bumps.authority,
         liquidity,
+        lock_lp_amount,
         open_time,
         ctx.accounts.creator.key(),
         ctx.accounts.amm_config.key(),
@@ -320,10 +377,56 @@ pub fn initialize(
         &ctx.accounts.lp_mint,
         ctx.accounts.observation_state.key(),
     );
+    pool_state.set_reserves(token_0_vault.amount, token_1_vault.amount);
 
     Ok(())
 }
 
+/// Create the metaplex metadata of the lp mint, the name and symbol are derived from the
+/// symbols of the pool tokens, and the authority PDA is the update authority
+fn create_lp_mint_metadata(ctx: &Context<Initialize>) -> Result<()> {
+    let token_0_symbol = get_token_symbol(
+        &ctx.accounts.token_0_mint.to_account_info(),
+        ctx.accounts.token_0_metadata.as_deref(),
+    )?;
+    let token_1_symbol = get_token_symbol(
+        &ctx.accounts.token_1_mint.to_account_info(),
+        ctx.accounts.token_1_metadata.as_deref(),
+    )?;
+    let (name, symbol) = lp_name_and_symbol(&token_0_symbol, &token_1_symbol);
+    let uri = ctx
+        .accounts
+        .lp_metadata_config
+        .render_uri(ctx.accounts.pool_state.key, &ctx.accounts.lp_mint.key());
+    create_metadata_accounts_v3(
+        CpiContext::new_with_signer(
+            ctx.accounts.metadata_program.to_account_info(),
+            CreateMetadataAccountsV3 {
+                metadata: ctx.accounts.lp_mint_metadata.to_account_info(),
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                mint_authority: ctx.accounts.authority.to_account_info(),
+                payer: ctx.accounts.creator.to_account_info(),
+                update_authority: ctx.accounts.authority.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            &[&[crate::AUTH_SEED.as_bytes(), &[ctx.bumps.authority]]],
+        ),
+        DataV2 {
+            name,
+            symbol,
+            uri,
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        },
+        true,
+        true,
+        None,
+    )
+}
+
 pub fn create_pool<'info>(
     payer: &AccountInfo<'info>,
     pool_account_info: &AccountInfo<'info>,
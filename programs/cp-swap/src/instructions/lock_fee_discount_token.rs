@@ -0,0 +1,116 @@
+use crate::states::*;
+use crate::utils::token::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use std::ops::DerefMut;
+
+#[derive(Accounts)]
+pub struct LockFeeDiscountToken<'info> {
+    /// 锁定折扣代币的钱包，支付创建锁定帐户的费用
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// 检查：折扣代币金库的所有者
+    #[account(
+        seeds = [
+            crate::AUTH_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    /// 锁定代币所获得的交易费折扣
+    pub fee_discount: Account<'info, FeeDiscount>,
+
+    /// 当前的折扣代币
+    #[account(
+        address = fee_discount.discount_mint,
+        mint::token_program = token_program,
+    )]
+    pub discount_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 初始化或更新钱包的锁定帐户
+    #[account(
+        init_if_needed,
+        seeds = [
+            FEE_DISCOUNT_LOCK_SEED.as_bytes(),
+            fee_discount.key().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump,
+        payer = owner,
+        space = FeeDiscountLock::LEN
+    )]
+    pub fee_discount_lock: Account<'info, FeeDiscountLock>,
+
+    /// 钱包的折扣代币帐户
+    #[account(
+        mut,
+        token::mint = discount_mint,
+        token::authority = owner,
+    )]
+    pub owner_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 检查：交易费折扣和折扣代币的金库，不存在时创建
+    #[account(
+        mut,
+        seeds = [
+            FEE_DISCOUNT_VAULT_SEED.as_bytes(),
+            fee_discount.key().as_ref(),
+            discount_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub lock_vault: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn lock_fee_discount_token<'info>(
+    ctx: Context<'_, '_, '_, 'info, LockFeeDiscountToken<'info>>,
+    amount: u64,
+) -> Result<()> {
+    require_gt!(amount, 0);
+    let block_timestamp = Clock::get()?.unix_timestamp as u64;
+    if ctx.accounts.lock_vault.data_is_empty() {
+        create_token_account(
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.lock_vault.to_account_info(),
+            &ctx.accounts.discount_mint.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            &[
+                FEE_DISCOUNT_VAULT_SEED.as_bytes(),
+                ctx.accounts.fee_discount.key().as_ref(),
+                ctx.accounts.discount_mint.key().as_ref(),
+                &[ctx.bumps.lock_vault][..],
+            ],
+        )?;
+    }
+
+    // 只记录金库实际收到的数量
+    let transfer_fee = get_transfer_fee(&ctx.accounts.discount_mint.to_account_info(), amount)?;
+    transfer_from_user_to_pool_vault(
+        ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_token.to_account_info(),
+        ctx.accounts.lock_vault.to_account_info(),
+        ctx.accounts.discount_mint.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        ctx.remaining_accounts,
+        amount,
+        ctx.accounts.discount_mint.decimals,
+    )?;
+
+    let fee_discount_lock = ctx.accounts.fee_discount_lock.deref_mut();
+    fee_discount_lock.bump = ctx.bumps.fee_discount_lock;
+    fee_discount_lock.fee_discount = ctx.accounts.fee_discount.key();
+    fee_discount_lock.owner = ctx.accounts.owner.key();
+    fee_discount_lock.lock(
+        ctx.accounts.discount_mint.key(),
+        amount.checked_sub(transfer_fee).unwrap(),
+        block_timestamp,
+    )
+}
@@ -13,3 +13,51 @@ pub use admin::*;
 
 pub mod swap_base_output;
 pub use swap_base_output::*;
+
+pub mod skim;
+pub use skim::*;
+
+pub mod observe_pool;
+pub use observe_pool::*;
+
+pub mod extend_observation;
+pub use extend_observation::*;
+
+pub mod create_price_feed;
+pub use create_price_feed::*;
+
+pub mod update_launch_fee;
+pub use update_launch_fee::*;
+
+pub mod update_direction_fee;
+pub use update_direction_fee::*;
+
+pub mod update_quote_mint;
+pub use update_quote_mint::*;
+
+pub mod update_anti_whale;
+pub use update_anti_whale::*;
+
+pub mod update_circuit_breaker;
+pub use update_circuit_breaker::*;
+
+pub mod trip_circuit_breaker;
+pub use trip_circuit_breaker::*;
+
+pub mod update_pool_hook;
+pub use update_pool_hook::*;
+
+pub mod create_pre_open_allow_list;
+pub use create_pre_open_allow_list::*;
+
+pub mod update_pre_open_allow_list;
+pub use update_pre_open_allow_list::*;
+
+pub mod claim_pre_open_pass;
+pub use claim_pre_open_pass::*;
+
+pub mod lock_fee_discount_token;
+pub use lock_fee_discount_token::*;
+
+pub mod unlock_fee_discount_token;
+pub use unlock_fee_discount_token::*;
@@ -0,0 +1,65 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+#[derive(Accounts)]
+pub struct ObservePool<'info> {
+    /// 任何人都可以更新观察值
+    pub payer: Signer<'info>,
+
+    /// 池状态
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// 持有 token_0 池代币的地址
+    #[account(
+        constraint = token_0_vault.key() == pool_state.load()?.token_0_vault @ ErrorCode::InvalidVault
+    )]
+    pub token_0_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 保存 token_1 池代币的地址
+    #[account(
+        constraint = token_1_vault.key() == pool_state.load()?.token_1_vault @ ErrorCode::InvalidVault
+    )]
+    pub token_1_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 该程序记录了最近的预言机观察结果
+    #[account(mut, address = pool_state.load()?.observation_key)]
+    pub observation_state: AccountLoader<'info, ObservationState>,
+}
+
+pub fn observe_pool(ctx: Context<ObservePool>) -> Result<()> {
+    let pool_state = ctx.accounts.pool_state.load()?;
+    let (mut observation_state, mut observation_ticks) =
+        oracle::load_observation_mut(&ctx.accounts.observation_state)?;
+    let last_timestamp = observation_state.observations
+        [observation_state.observation_index as usize]
+        .block_timestamp;
+    let block_timestamp = oracle::block_timestamp();
+    require_gte!(
+        block_timestamp.saturating_sub(last_timestamp),
+        OBSERVATION_UPDATE_DURATION_DEFAULT,
+        ErrorCode::ObservationNotDue
+    );
+    pool_state.observe(
+        &mut observation_state,
+        observation_ticks.as_deref_mut(),
+        ctx.accounts.token_0_vault.amount,
+        ctx.accounts.token_1_vault.amount,
+        block_timestamp,
+    );
+    let (reserve_0, reserve_1) = pool_state.vault_amount_without_fee(
+        ctx.accounts.token_0_vault.amount,
+        ctx.accounts.token_1_vault.amount,
+    );
+    refresh_price_feed(
+        ctx.accounts.pool_state.key(),
+        &pool_state,
+        &observation_state,
+        observation_ticks.as_deref(),
+        reserve_0,
+        reserve_1,
+        ctx.remaining_accounts,
+    )?;
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::utils::token::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
+
+#[derive(Accounts)]
+pub struct Skim<'info> {
+    /// 任何人都可以将金库的盈余转出
+    pub payer: Signer<'info>,
+
+    /// 检查：金库和 lp 铸币机构
+    #[account(
+        seeds = [
+            crate::AUTH_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    /// 池状态存储记账储备
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// 持有 token_0 池代币的地址
+    #[account(
+        mut,
+        constraint = token_0_vault.key() == pool_state.load()?.token_0_vault @ ErrorCode::InvalidVault
+    )]
+    pub token_0_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 保存 token_1 池代币的地址
+    #[account(
+        mut,
+        constraint = token_1_vault.key() == pool_state.load()?.token_1_vault @ ErrorCode::InvalidVault
+    )]
+    pub token_1_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// token_0金库的铸币厂
+    #[account(
+        address = token_0_vault.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// token_1金库的铸币厂
+    #[account(
+        address = token_1_vault.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 接收 token_0 盈余的代币账户
+    #[account(
+        mut,
+        token::mint = vault_0_mint
+    )]
+    pub recipient_token_0_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 接收 token_1 盈余的代币账户
+    #[account(
+        mut,
+        token::mint = vault_1_mint
+    )]
+    pub recipient_token_1_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 执行代币传输的 SPL 程序
+    pub token_program: Program<'info, Token>,
+
+    /// SPL 计划 2022 执行代币转账
+    pub token_program_2022: Program<'info, Token2022>,
+}
+
+pub fn skim<'info>(ctx: Context<'_, '_, '_, 'info, Skim<'info>>) -> Result<()> {
+    // 只转出盈余，记账储备保持不变
+    let (surplus_0, surplus_1, auth_bump) = {
+        let pool_state = ctx.accounts.pool_state.load()?;
+        let (surplus_0, surplus_1) = pool_state.surplus_amount(
+            ctx.accounts.token_0_vault.amount,
+            ctx.accounts.token_1_vault.amount,
+        );
+        (surplus_0, surplus_1, pool_state.auth_bump)
+    };
+
+    transfer_from_pool_vault_to_user(
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.token_0_vault.to_account_info(),
+        ctx.accounts.recipient_token_0_account.to_account_info(),
+        ctx.accounts.vault_0_mint.to_account_info(),
+        if ctx.accounts.vault_0_mint.to_account_info().owner == ctx.accounts.token_program.key {
+            ctx.accounts.token_program.to_account_info()
+        } else {
+            ctx.accounts.token_program_2022.to_account_info()
+        },
+        ctx.remaining_accounts,
+        surplus_0,
+        ctx.accounts.vault_0_mint.decimals,
+        &[&[crate::AUTH_SEED.as_bytes(), &[auth_bump]]],
+    )?;
+
+    transfer_from_pool_vault_to_user(
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.token_1_vault.to_account_info(),
+        ctx.accounts.recipient_token_1_account.to_account_info(),
+        ctx.accounts.vault_1_mint.to_account_info(),
+        if ctx.accounts.vault_1_mint.to_account_info().owner == ctx.accounts.token_program.key {
+            ctx.accounts.token_program.to_account_info()
+        } else {
+            ctx.accounts.token_program_2022.to_account_info()
+        },
+        ctx.remaining_accounts,
+        surplus_1,
+        ctx.accounts.vault_1_mint.decimals,
+        &[&[crate::AUTH_SEED.as_bytes(), &[auth_bump]]],
+    )?;
+
+    Ok(())
+}
@@ -1,11 +1,12 @@
 use crate::curve::calculator::CurveCalculator;
-use crate::curve::TradeDirection;
+use crate::curve::{Fees, TradeDirection};
 use crate::error::ErrorCode;
 use crate::states::*;
 use crate::utils::token::*;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use spl_token_2022::extension::StateWithExtensions;
 
 #[derive(Accounts)]
 pub struct Swap<'info> {
@@ -71,14 +72,59 @@ pub struct Swap<'info> {
     /// 该程序记录了最近的预言机观察结果
     #[account(mut, address = pool_state.load()?.observation_key)]
     pub observation_state: AccountLoader<'info, ObservationState>,
+
+    /// 可选的推荐人代币账户，收取协议费中的推荐费
+    #[account(mut)]
+    pub referrer_token_account: Option<UncheckedAccount<'info>>,
+}
+
+/// Get the owner of the referrer token account, which must be a token account of the fee token
+/// other than the fee vault, and not owned by the payer
+pub fn get_referrer(
+    referrer_token_account: &AccountInfo,
+    payer: &Pubkey,
+    fee_vault: &Pubkey,
+    fee_token_mint: &Pubkey,
+    fee_token_program: &Pubkey,
+) -> Result<Pubkey> {
+    if referrer_token_account.owner != fee_token_program || referrer_token_account.key == fee_vault
+    {
+        return err!(ErrorCode::InvalidReferrer);
+    }
+    let data = referrer_token_account.try_borrow_data()?;
+    let token_account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)
+        .map_err(|_| ErrorCode::InvalidReferrer)?;
+    if token_account.base.mint != *fee_token_mint || token_account.base.owner == *payer {
+        return err!(ErrorCode::InvalidReferrer);
+    }
+    Ok(token_account.base.owner)
 }
 
-pub fn swap_base_input(ctx: Context<Swap>, amount_in: u64, minimum_amount_out: u64) -> Result<()> {
+pub fn swap_base_input<'info>(
+    ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Result<()> {
     let block_timestamp = solana_program::clock::Clock::get()?.unix_timestamp as u64;
     let pool_id = ctx.accounts.pool_state.key();
     let pool_state = &mut ctx.accounts.pool_state.load_mut()?;
-    if !pool_state.get_status_by_bit(PoolStatusBitIndex::Swap)
-        || block_timestamp < pool_state.open_time
+    if !pool_state.get_status_by_bit(PoolStatusBitIndex::Swap) {
+        return err!(ErrorCode::NotApproved);
+    }
+    // 熔断器暂停期间拒绝交换
+    let circuit_breaker = find_circuit_breaker(pool_id, pool_state, ctx.remaining_accounts)?;
+    if let Some(circuit_breaker) = &circuit_breaker {
+        if circuit_breaker.paused(block_timestamp) {
+            return err!(ErrorCode::CircuitBreakerPaused);
+        }
+    }
+    // 开放时间之前只有允许名单中的钱包可以交换
+    if block_timestamp < pool_state.open_time
+        && !is_pre_open_trader(
+            &pool_state.pre_open_allow_list,
+            ctx.accounts.payer.key,
+            ctx.remaining_accounts,
+        )?
     {
         return err!(ErrorCode::NotApproved);
     }
@@ -139,28 +185,78 @@ pub fn swap_base_input(ctx: Context<Swap>, amount_in: u64, minimum_amount_out: u
     } else {
         return err!(ErrorCode::InvalidVault);
     };
+    // 启动阶段的交易费率高于配置的交易费率
+    let trade_fee_rate = pool_state.trade_fee_rate(
+        ctx.accounts.amm_config.trade_fee_rate,
+        trade_direction,
+        block_timestamp,
+    );
+    // 锁定折扣代币的交换者享受交易费折扣
+    let trade_fee_rate = get_discounted_trade_fee_rate(
+        ctx.accounts.amm_config.key(),
+        ctx.accounts.payer.key,
+        trade_fee_rate,
+        ctx.remaining_accounts,
+    );
+    // 输出代币为报价代币时从输出端收取交易费
+    let fee_on_output = pool_state.fee_on_output(trade_direction);
     let constant_before = u128::from(total_input_token_amount)
         .checked_mul(u128::from(total_output_token_amount))
         .unwrap();
 
-    let result = CurveCalculator::swap_base_input(
-        u128::from(actual_amount_in),
-        u128::from(total_input_token_amount),
-        u128::from(total_output_token_amount),
-        ctx.accounts.amm_config.trade_fee_rate,
-        ctx.accounts.amm_config.protocol_fee_rate,
-        ctx.accounts.amm_config.fund_fee_rate,
-    )
+    let result = if fee_on_output {
+        CurveCalculator::swap_base_input_with_output_fee(
+            u128::from(actual_amount_in),
+            u128::from(total_input_token_amount),
+            u128::from(total_output_token_amount),
+            trade_fee_rate,
+            ctx.accounts.amm_config.protocol_fee_rate,
+            ctx.accounts.amm_config.fund_fee_rate,
+        )
+    } else {
+        CurveCalculator::swap_base_input(
+            u128::from(actual_amount_in),
+            u128::from(total_input_token_amount),
+            u128::from(total_output_token_amount),
+            trade_fee_rate,
+            ctx.accounts.amm_config.protocol_fee_rate,
+            ctx.accounts.amm_config.fund_fee_rate,
+        )
+    }
     .ok_or(ErrorCode::ZeroTradingTokens)?;
 
-    let constant_after = u128::from(
-        result
-            .new_swap_source_amount
-            .checked_sub(result.trade_fee)
-            .unwrap(),
-    )
-    .checked_mul(u128::from(result.new_swap_destination_amount))
-    .unwrap();
+    // 启动窗口内限制单笔交易的输出数量
+    if let Some(max_amount_out) =
+        pool_state.anti_whale_max_output(block_timestamp, total_output_token_amount)
+    {
+        require_gte!(
+            u128::from(max_amount_out),
+            result.destination_amount_swapped,
+            ErrorCode::ExceededMaxTradeSize
+        );
+    }
+
+    // 交易费不计入交换后的常数
+    let (new_swap_source_amount, new_swap_destination_amount) = if fee_on_output {
+        (
+            result.new_swap_source_amount,
+            result
+                .new_swap_destination_amount
+                .checked_sub(result.trade_fee)
+                .unwrap(),
+        )
+    } else {
+        (
+            result
+                .new_swap_source_amount
+                .checked_sub(result.trade_fee)
+                .unwrap(),
+            result.new_swap_destination_amount,
+        )
+    };
+    let constant_after = new_swap_source_amount
+        .checked_mul(new_swap_destination_amount)
+        .unwrap();
     #[cfg(feature = "enable-log")]
     msg!(
         "source_amount_swapped:{}, destination_amount_swapped:{}, trade_fee:{}, constant_before:{},constant_after:{}",
@@ -193,25 +289,142 @@ pub fn swap_base_input(ctx: Context<Swap>, amount_in: u64, minimum_amount_out: u
 
     let protocol_fee = u64::try_from(result.protocol_fee).unwrap();
     let fund_fee = u64::try_from(result.fund_fee).unwrap();
+    // 交易费所在的金库和代币
+    let (fee_vault, fee_token_mint, fee_token_program) = if fee_on_output {
+        (
+            &ctx.accounts.output_vault,
+            &ctx.accounts.output_token_mint,
+            &ctx.accounts.output_token_program,
+        )
+    } else {
+        (
+            &ctx.accounts.input_vault,
+            &ctx.accounts.input_token_mint,
+            &ctx.accounts.input_token_program,
+        )
+    };
+    // 推荐费从协议费中支付给推荐人
+    let (referrer, referral_fee) = match &ctx.accounts.referrer_token_account {
+        Some(referrer_token_account) => (
+            get_referrer(
+                referrer_token_account,
+                ctx.accounts.payer.key,
+                &fee_vault.key(),
+                &fee_token_mint.key(),
+                fee_token_program.key,
+            )?,
+            u64::try_from(
+                Fees::referral_fee(
+                    u128::from(protocol_fee),
+                    ctx.accounts.amm_config.referral_fee_rate,
+                )
+                .unwrap(),
+            )
+            .unwrap(),
+        ),
+        None => (Pubkey::default(), 0),
+    };
+    let protocol_fee = protocol_fee.checked_sub(referral_fee).unwrap();
+
+    // 交换后的记账储备，收取的费用不计入储备
+    let (new_input_reserve, new_output_reserve) = {
+        let fees = protocol_fee + fund_fee + referral_fee;
+        let new_input_reserve = u64::try_from(result.new_swap_source_amount).unwrap();
+        let new_output_reserve = u64::try_from(result.new_swap_destination_amount).unwrap();
+        if fee_on_output {
+            (
+                new_input_reserve,
+                new_output_reserve.checked_sub(fees).unwrap(),
+            )
+        } else {
+            (
+                new_input_reserve.checked_sub(fees).unwrap(),
+                new_output_reserve,
+            )
+        }
+    };
+    // 交换后的价格偏离时间加权平均价格过多时拒绝交换
+    if let Some(circuit_breaker) = &circuit_breaker {
+        let (new_reserve_0, new_reserve_1) = match trade_direction {
+            TradeDirection::ZeroForOne => (new_input_reserve, new_output_reserve),
+            TradeDirection::OneForZero => (new_output_reserve, new_input_reserve),
+        };
+        let (observation_state, observation_ticks) =
+            oracle::load_observation_mut(&ctx.accounts.observation_state)?;
+        circuit_breaker.check(
+            &observation_state,
+            observation_ticks.as_deref(),
+            oracle::tick_at_reserves(new_reserve_0, new_reserve_1),
+        )?;
+    }
 
+    let fee_in_token_0 = if fee_on_output {
+        trade_direction == TradeDirection::OneForZero
+    } else {
+        trade_direction == TradeDirection::ZeroForOne
+    };
+    if fee_in_token_0 {
+        pool_state.protocol_fees_token_0 = pool_state
+            .protocol_fees_token_0
+            .checked_add(protocol_fee)
+            .unwrap();
+        pool_state.fund_fees_token_0 = pool_state.fund_fees_token_0.checked_add(fund_fee).unwrap();
+    } else {
+        pool_state.protocol_fees_token_1 = pool_state
+            .protocol_fees_token_1
+            .checked_add(protocol_fee)
+            .unwrap();
+        pool_state.fund_fees_token_1 = pool_state.fund_fees_token_1.checked_add(fund_fee).unwrap();
+    }
+
+    // 交易量和留给流动性提供者的交易费
+    let input_amount = u64::try_from(result.source_amount_swapped).unwrap();
+    let output_amount = u64::try_from(result.destination_amount_swapped).unwrap();
+    let lp_fee = u64::try_from(result.trade_fee)
+        .unwrap()
+        .checked_sub(protocol_fee + fund_fee + referral_fee)
+        .unwrap();
+    // 限制金库在滚动窗口内的流出量
     match trade_direction {
         TradeDirection::ZeroForOne => {
-            pool_state.protocol_fees_token_0 = pool_state
-                .protocol_fees_token_0
-                .checked_add(protocol_fee)
-                .unwrap();
-            pool_state.fund_fees_token_0 =
-                pool_state.fund_fees_token_0.checked_add(fund_fee).unwrap();
+            pool_state.record_outflow(
+                0,
+                output_amount,
+                total_input_token_amount,
+                total_output_token_amount,
+                Clock::get()?.slot,
+            )?;
+            pool_state.set_reserves(new_input_reserve, new_output_reserve);
+            if fee_on_output {
+                pool_state.record_swap(input_amount, output_amount, 0, lp_fee);
+            } else {
+                pool_state.record_swap(input_amount, output_amount, lp_fee, 0);
+            }
         }
         TradeDirection::OneForZero => {
-            pool_state.protocol_fees_token_1 = pool_state
-                .protocol_fees_token_1
-                .checked_add(protocol_fee)
-                .unwrap();
-            pool_state.fund_fees_token_1 =
-                pool_state.fund_fees_token_1.checked_add(fund_fee).unwrap();
+            pool_state.record_outflow(
+                output_amount,
+                0,
+                total_output_token_amount,
+                total_input_token_amount,
+                Clock::get()?.slot,
+            )?;
+            pool_state.set_reserves(new_output_reserve, new_input_reserve);
+            if fee_on_output {
+                pool_state.record_swap(output_amount, input_amount, lp_fee, 0);
+            } else {
+                pool_state.record_swap(output_amount, input_amount, 0, lp_fee);
+            }
         }
-    };
+    }
+
+    // 价格影响超过配置上限时拒绝交换
+    ctx.accounts
+        .amm_config
+        .check_price_impact(price_impact_bps(
+            (token_0_price_x64, token_1_price_x64),
+            pool_state.token_price_x32(pool_state.reserve_0, pool_state.reserve_1),
+        ))?;
 
     emit!(SwapEvent {
         pool_id,
@@ -221,7 +434,12 @@ pub fn swap_base_input(ctx: Context<Swap>, amount_in: u64, minimum_amount_out: u
         output_amount: u64::try_from(result.destination_amount_swapped).unwrap(),
         input_transfer_fee,
         output_transfer_fee,
-        base_input: true
+        base_input: true,
+        trade_fee_rate,
+        trade_fee: u64::try_from(result.trade_fee).unwrap(),
+        fee_on_output,
+        referrer,
+        referral_fee,
     });
     require_gte!(constant_after, constant_before);
 
@@ -231,6 +449,7 @@ pub fn swap_base_input(ctx: Context<Swap>, amount_in: u64, minimum_amount_out: u
         ctx.accounts.input_vault.to_account_info(),
         ctx.accounts.input_token_mint.to_account_info(),
         ctx.accounts.input_token_program.to_account_info(),
+        ctx.remaining_accounts,
         input_transfer_amount,
         ctx.accounts.input_token_mint.decimals,
     )?;
@@ -241,18 +460,78 @@ pub fn swap_base_input(ctx: Context<Swap>, amount_in: u64, minimum_amount_out: u
         ctx.accounts.output_token_account.to_account_info(),
         ctx.accounts.output_token_mint.to_account_info(),
         ctx.accounts.output_token_program.to_account_info(),
+        ctx.remaining_accounts,
         output_transfer_amount,
         ctx.accounts.output_token_mint.decimals,
         &[&[crate::AUTH_SEED.as_bytes(), &[pool_state.auth_bump]]],
     )?;
 
+    if let Some(referrer_token_account) = &ctx.accounts.referrer_token_account {
+        transfer_from_pool_vault_to_user(
+            ctx.accounts.authority.to_account_info(),
+            fee_vault.to_account_info(),
+            referrer_token_account.to_account_info(),
+            fee_token_mint.to_account_info(),
+            fee_token_program.to_account_info(),
+            ctx.remaining_accounts,
+            referral_fee,
+            fee_token_mint.decimals,
+            &[&[crate::AUTH_SEED.as_bytes(), &[pool_state.auth_bump]]],
+        )?;
+    }
+
     // 将之前的价格更新为观察值
-    ctx.accounts.observation_state.load_mut()?.update(
-        oracle::block_timestamp(),
-        token_0_price_x64,
-        token_1_price_x64,
-    );
+    let tick = match trade_direction {
+        TradeDirection::ZeroForOne => {
+            oracle::tick_at_reserves(total_input_token_amount, total_output_token_amount)
+        }
+        TradeDirection::OneForZero => {
+            oracle::tick_at_reserves(total_output_token_amount, total_input_token_amount)
+        }
+    };
+    {
+        let (mut observation_state, mut observation_ticks) =
+            oracle::load_observation_mut(&ctx.accounts.observation_state)?;
+        observation_state.update(
+            observation_ticks.as_deref_mut(),
+            oracle::block_timestamp(),
+            token_0_price_x64,
+            token_1_price_x64,
+            tick,
+        );
+        refresh_price_feed(
+            ctx.accounts.pool_state.key(),
+            pool_state,
+            &observation_state,
+            observation_ticks.as_deref(),
+            pool_state.reserve_0,
+            pool_state.reserve_1,
+            ctx.remaining_accounts,
+        )?;
+        // 交换后的价格偏离外部参考价格过多时拒绝交换
+        check_price_guard(pool_id, pool_state, ctx.remaining_accounts, block_timestamp)?;
+    }
     pool_state.recent_epoch = Clock::get()?.epoch;
 
+    // 调用池的钩子程序
+    let (token_0_amount, token_1_amount) = match trade_direction {
+        TradeDirection::ZeroForOne => (input_amount, output_amount),
+        TradeDirection::OneForZero => (output_amount, input_amount),
+    };
+    invoke_pool_hook(
+        pool_state,
+        &ctx.accounts.payer.to_account_info(),
+        PoolHookAction {
+            pool_id,
+            user: ctx.accounts.payer.key(),
+            kind: PoolHookActionKind::Swap as u8,
+            trade_direction: trade_direction as u8,
+            token_0_amount,
+            token_1_amount,
+            lp_amount: 0,
+        },
+        ctx.remaining_accounts,
+    )?;
+
     Ok(())
 }
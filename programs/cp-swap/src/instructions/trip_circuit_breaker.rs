@@ -0,0 +1,38 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct TripCircuitBreaker<'info> {
+    /// 池创建者或管理员
+    #[account(
+        constraint = authority.key() == pool_state.load()?.pool_creator
+            || authority.key() == crate::admin::ID @ ErrorCode::InvalidOwner
+    )]
+    pub authority: Signer<'info>,
+
+    /// 要暂停交换的池
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// 池的熔断器帐户
+    #[account(
+        mut,
+        seeds = [
+            CIRCUIT_BREAKER_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+        ],
+        bump = circuit_breaker.bump,
+    )]
+    pub circuit_breaker: Account<'info, CircuitBreaker>,
+}
+
+pub fn trip_circuit_breaker(ctx: Context<TripCircuitBreaker>) -> Result<()> {
+    let block_timestamp = Clock::get()?.unix_timestamp as u64;
+    let paused_until = ctx.accounts.circuit_breaker.trip(block_timestamp)?;
+    emit!(CircuitBreakerEvent {
+        pool_id: ctx.accounts.pool_state.key(),
+        authority: ctx.accounts.authority.key(),
+        paused_until,
+    });
+    Ok(())
+}
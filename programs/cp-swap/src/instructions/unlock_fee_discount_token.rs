@@ -0,0 +1,85 @@
+use crate::states::*;
+use crate::utils::token::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct UnlockFeeDiscountToken<'info> {
+    /// 锁定折扣代币的钱包
+    pub owner: Signer<'info>,
+
+    /// 检查：折扣代币金库的所有者
+    #[account(
+        seeds = [
+            crate::AUTH_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    /// 锁定代币所获得的交易费折扣
+    pub fee_discount: Account<'info, FeeDiscount>,
+
+    /// 钱包的锁定帐户
+    #[account(
+        mut,
+        seeds = [
+            FEE_DISCOUNT_LOCK_SEED.as_bytes(),
+            fee_discount.key().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump = fee_discount_lock.bump,
+        has_one = owner,
+    )]
+    pub fee_discount_lock: Account<'info, FeeDiscountLock>,
+
+    /// 锁定的代币，可能是之前的折扣代币
+    #[account(
+        address = fee_discount_lock.mint,
+        mint::token_program = token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// 接收解锁代币的帐户
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub owner_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 交易费折扣和锁定代币的金库
+    #[account(
+        mut,
+        seeds = [
+            FEE_DISCOUNT_VAULT_SEED.as_bytes(),
+            fee_discount.key().as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub lock_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn unlock_fee_discount_token<'info>(
+    ctx: Context<'_, '_, '_, 'info, UnlockFeeDiscountToken<'info>>,
+) -> Result<()> {
+    let block_timestamp = Clock::get()?.unix_timestamp as u64;
+    // 锁定时间未满时拒绝解锁
+    let amount = ctx
+        .accounts
+        .fee_discount_lock
+        .unlock(ctx.accounts.fee_discount.min_lock_duration, block_timestamp)?;
+    transfer_from_pool_vault_to_user(
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.lock_vault.to_account_info(),
+        ctx.accounts.owner_token.to_account_info(),
+        ctx.accounts.mint.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        ctx.remaining_accounts,
+        amount,
+        ctx.accounts.mint.decimals,
+        &[&[crate::AUTH_SEED.as_bytes(), &[ctx.bumps.authority]]],
+    )
+}
@@ -0,0 +1,40 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateAntiWhale<'info> {
+    /// 池创建者或管理员
+    #[account(
+        constraint = authority.key() == pool_state.load()?.pool_creator
+            || authority.key() == crate::admin::ID @ ErrorCode::InvalidOwner
+    )]
+    pub authority: Signer<'info>,
+
+    /// 要设置反鲸鱼窗口的池，必须在开放时间之前
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+}
+
+pub fn update_anti_whale(
+    ctx: Context<UpdateAntiWhale>,
+    duration: u32,
+    max_output_bps: u16,
+) -> Result<()> {
+    let block_timestamp = Clock::get()?.unix_timestamp as u64;
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    // the window can't be changed once the pool is open
+    if block_timestamp >= pool_state.open_time {
+        return err!(ErrorCode::NotApproved);
+    }
+    if max_output_bps != 0 {
+        require_gte!(
+            BPS_DENOMINATOR,
+            u64::from(max_output_bps),
+            ErrorCode::InvalidInput
+        );
+        require_gt!(duration, 0, ErrorCode::InvalidInput);
+    }
+    pool_state.set_anti_whale(duration, max_output_bps);
+    Ok(())
+}
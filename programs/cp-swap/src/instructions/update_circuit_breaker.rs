@@ -0,0 +1,64 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use std::ops::DerefMut;
+
+#[derive(Accounts)]
+pub struct UpdateCircuitBreaker<'info> {
+    /// 池创建者或管理员，支付创建熔断器的费用
+    #[account(
+        mut,
+        constraint = authority.key() == pool_state.load()?.pool_creator
+            || authority.key() == crate::admin::ID @ ErrorCode::InvalidOwner
+    )]
+    pub authority: Signer<'info>,
+
+    /// 要设置熔断器的池
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// 初始化或更新池的熔断器帐户
+    #[account(
+        init_if_needed,
+        seeds = [
+            CIRCUIT_BREAKER_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+        ],
+        bump,
+        payer = authority,
+        space = CircuitBreaker::LEN
+    )]
+    pub circuit_breaker: Account<'info, CircuitBreaker>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn update_circuit_breaker(
+    ctx: Context<UpdateCircuitBreaker>,
+    max_deviation_bps: u16,
+    twap_window: u16,
+    cooldown: u32,
+) -> Result<()> {
+    if max_deviation_bps != 0 {
+        // the twap window must be covered by the observations
+        require_gt!(twap_window, 0, ErrorCode::InvalidInput);
+        require_gte!(
+            (OBSERVATION_NUM as u64 - 1) * OBSERVATION_UPDATE_DURATION_DEFAULT,
+            u64::from(twap_window),
+            ErrorCode::InvalidInput
+        );
+    }
+    require_gte!(
+        MAX_CIRCUIT_BREAKER_COOLDOWN,
+        cooldown,
+        ErrorCode::InvalidInput
+    );
+    let circuit_breaker = ctx.accounts.circuit_breaker.deref_mut();
+    circuit_breaker.bump = ctx.bumps.circuit_breaker;
+    circuit_breaker.pool_id = ctx.accounts.pool_state.key();
+    circuit_breaker.set(max_deviation_bps, twap_window, cooldown);
+
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    pool_state.set_guard_by_bit(PoolGuardBitIndex::CircuitBreaker, max_deviation_bps != 0);
+    Ok(())
+}
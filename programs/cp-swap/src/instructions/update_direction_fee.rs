@@ -0,0 +1,39 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateDirectionFee<'info> {
+    /// 池创建者或管理员
+    #[account(
+        constraint = authority.key() == pool_state.load()?.pool_creator
+            || authority.key() == crate::admin::ID @ ErrorCode::InvalidOwner
+    )]
+    pub authority: Signer<'info>,
+
+    /// 池所属的配置，限制买卖费率的上限
+    #[account(address = pool_state.load()?.amm_config)]
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+
+    /// 要设置买卖费率的池，必须在开放时间之前
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+}
+
+pub fn update_direction_fee(
+    ctx: Context<UpdateDirectionFee>,
+    zero_for_one_fee_rate: u64,
+    one_for_zero_fee_rate: u64,
+) -> Result<()> {
+    let block_timestamp = Clock::get()?.unix_timestamp as u64;
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    // the fee rates can't be changed once the pool is open
+    if block_timestamp >= pool_state.open_time {
+        return err!(ErrorCode::NotApproved);
+    }
+    let amm_config = &ctx.accounts.amm_config;
+    amm_config.check_direction_fee_rate(zero_for_one_fee_rate)?;
+    amm_config.check_direction_fee_rate(one_for_zero_fee_rate)?;
+    pool_state.set_direction_fee_rates(zero_for_one_fee_rate, one_for_zero_fee_rate);
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use crate::curve::fees::FEE_RATE_DENOMINATOR_VALUE;
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateLaunchFee<'info> {
+    /// 池创建者或管理员
+    #[account(
+        constraint = authority.key() == pool_state.load()?.pool_creator
+            || authority.key() == crate::admin::ID @ ErrorCode::InvalidOwner
+    )]
+    pub authority: Signer<'info>,
+
+    /// 池所属的配置，提供交易费率
+    #[account(address = pool_state.load()?.amm_config)]
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+
+    /// 要设置启动费用的池，必须在开放时间之前
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+}
+
+pub fn update_launch_fee(
+    ctx: Context<UpdateLaunchFee>,
+    launch_fee_rate: u64,
+    duration: u32,
+    mode: u8,
+) -> Result<()> {
+    let block_timestamp = Clock::get()?.unix_timestamp as u64;
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    // the schedule can't be changed once the pool is open
+    if block_timestamp >= pool_state.open_time {
+        return err!(ErrorCode::NotApproved);
+    }
+    let mode = LaunchFeeMode::try_from(mode).map_err(|_| ErrorCode::InvalidInput)?;
+    if launch_fee_rate != 0 {
+        require_gt!(
+            launch_fee_rate,
+            ctx.accounts.amm_config.trade_fee_rate,
+            ErrorCode::InvalidInput
+        );
+        require_gt!(
+            FEE_RATE_DENOMINATOR_VALUE,
+            launch_fee_rate,
+            ErrorCode::InvalidInput
+        );
+        require_gt!(duration, 0, ErrorCode::InvalidInput);
+    }
+    pool_state.set_launch_fee(launch_fee_rate, duration, mode);
+    Ok(())
+}
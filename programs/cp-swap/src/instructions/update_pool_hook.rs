@@ -0,0 +1,57 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+use std::ops::DerefMut;
+
+#[derive(Accounts)]
+pub struct UpdatePoolHook<'info> {
+    /// 池创建者或管理员，支付创建钩子的费用
+    #[account(
+        mut,
+        constraint = authority.key() == pool_state.load()?.pool_creator
+            || authority.key() == crate::admin::ID @ ErrorCode::InvalidOwner
+    )]
+    pub authority: Signer<'info>,
+
+    /// 要设置钩子的池
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// 初始化或更新池的钩子帐户
+    #[account(
+        init_if_needed,
+        seeds = [
+            POOL_HOOK_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+        ],
+        bump,
+        payer = authority,
+        space = PoolHook::LEN
+    )]
+    pub pool_hook: Account<'info, PoolHook>,
+
+    /// CHECK: 交换、存款和取款之后调用的钩子程序
+    pub hook_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn update_pool_hook(ctx: Context<UpdatePoolHook>, policy: u8) -> Result<()> {
+    let hook_policy = PoolHookPolicy::from_u8(policy).ok_or(ErrorCode::InvalidInput)?;
+    if hook_policy != PoolHookPolicy::None {
+        require!(
+            ctx.accounts.hook_program.executable,
+            ErrorCode::InvalidInput
+        );
+    }
+    let pool_hook = ctx.accounts.pool_hook.deref_mut();
+    pool_hook.bump = ctx.bumps.pool_hook;
+    pool_hook.pool_id = ctx.accounts.pool_state.key();
+    pool_hook.hook_program = ctx.accounts.hook_program.key();
+    pool_hook.policy = hook_policy as u8;
+
+    // 注册钩子后交换、存款和取款都必须传入钩子帐户
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    pool_state.set_guard_by_bit(PoolGuardBitIndex::Hook, hook_policy != PoolHookPolicy::None);
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdatePreOpenAllowList<'info> {
+    /// 池创建者或管理员
+    #[account(
+        constraint = authority.key() == pool_state.load()?.pool_creator
+            || authority.key() == crate::admin::ID @ ErrorCode::InvalidOwner
+    )]
+    pub authority: Signer<'info>,
+
+    /// 允许名单所属的池
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// 要更改的允许名单帐户
+    #[account(
+        mut,
+        address = pool_state.load()?.pre_open_allow_list
+    )]
+    pub allow_list: AccountLoader<'info, PreOpenAllowList>,
+}
+
+pub fn update_pre_open_allow_list(
+    ctx: Context<UpdatePreOpenAllowList>,
+    param: u8,
+    merkle_root: [u8; 32],
+) -> Result<()> {
+    let mut allow_list = ctx.accounts.allow_list.load_mut()?;
+    let keys = ctx.remaining_accounts.iter().map(|account| *account.key);
+    match param {
+        0 => {
+            for key in keys {
+                allow_list.add_key(key)?;
+            }
+        }
+        1 => {
+            for key in keys {
+                allow_list.remove_key(key)?;
+            }
+        }
+        2 => allow_list.merkle_root = merkle_root,
+        _ => return err!(ErrorCode::InvalidInput),
+    }
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateQuoteMint<'info> {
+    /// 池创建者或管理员
+    #[account(
+        constraint = authority.key() == pool_state.load()?.pool_creator
+            || authority.key() == crate::admin::ID @ ErrorCode::InvalidOwner
+    )]
+    pub authority: Signer<'info>,
+
+    /// 要设置报价代币的池
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+}
+
+pub fn update_quote_mint(ctx: Context<UpdateQuoteMint>, quote_mint: Pubkey) -> Result<()> {
+    let block_timestamp = Clock::get()?.unix_timestamp as u64;
+    let mut pool_state = ctx.accounts.pool_state.load_mut()?;
+    // the pool creator can only change it before the pool opens
+    if ctx.accounts.authority.key() != crate::admin::ID && block_timestamp >= pool_state.open_time {
+        return err!(ErrorCode::NotApproved);
+    }
+    if quote_mint != Pubkey::default()
+        && quote_mint != pool_state.token_0_mint
+        && quote_mint != pool_state.token_1_mint
+    {
+        return err!(ErrorCode::InvalidInput);
+    }
+    pool_state.quote_mint = quote_mint;
+    Ok(())
+}
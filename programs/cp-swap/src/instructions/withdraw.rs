@@ -30,11 +30,12 @@ pub struct Withdraw<'info> {
 
     /// 所有者 lp 代币账户
     #[account(
-        mut, 
+        mut,
         token::authority = owner
     )]
     pub owner_lp_token: Box<InterfaceAccount<'info, TokenAccount>>,
-/// 接收token_0的token账户，
+
+    /// 接收token_0的token账户，
     #[account(
         mut,
         token::mint = token_0_vault.mint,
@@ -93,10 +94,14 @@ pub struct Withdraw<'info> {
         address = spl_memo::id()
     )]
     pub memo_program: UncheckedAccount<'info>,
+
+    /// 该程序记录了最近的预言机观察结果
+    #[account(mut, address = pool_state.load()?.observation_key)]
+    pub observation_state: AccountLoader<'info, ObservationState>,
 }
 
-pub fn withdraw(
-    ctx: Context<Withdraw>,
+pub fn withdraw<'info>(
+    ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>,
     lp_token_amount: u64,
     minimum_token_0_amount: u64,
     minimum_token_1_amount: u64,
@@ -108,10 +113,28 @@ pub fn withdraw(
     if !pool_state.get_status_by_bit(PoolStatusBitIndex::Withdraw) {
         return err!(ErrorCode::NotApproved);
     }
+    // 锁定的 LP 永远不能取出
+    require_gte!(
+        pool_state.unlocked_lp_supply(),
+        lp_token_amount,
+        ErrorCode::ExceededLockedLiquidity
+    );
     let (total_token_0_amount, total_token_1_amount) = pool_state.vault_amount_without_fee(
         ctx.accounts.token_0_vault.amount,
         ctx.accounts.token_1_vault.amount,
     );
+    // 将取款前的价格更新为观察值
+    {
+        let (mut observation_state, mut observation_ticks) =
+            oracle::load_observation_mut(&ctx.accounts.observation_state)?;
+        pool_state.observe(
+            &mut observation_state,
+            observation_ticks.as_deref_mut(),
+            ctx.accounts.token_0_vault.amount,
+            ctx.accounts.token_1_vault.amount,
+            oracle::block_timestamp(),
+        );
+    }
     let results = CurveCalculator::lp_tokens_to_trading_tokens(
         u128::from(lp_token_amount),
         u128::from(pool_state.lp_supply),
@@ -174,7 +197,19 @@ pub fn withdraw(
         return Err(ErrorCode::ExceededSlippage.into());
     }
 
+    // 限制金库在滚动窗口内的流出量
+    pool_state.record_outflow(
+        token_0_amount,
+        token_1_amount,
+        total_token_0_amount,
+        total_token_1_amount,
+        Clock::get()?.slot,
+    )?;
     pool_state.lp_supply = pool_state.lp_supply.checked_sub(lp_token_amount).unwrap();
+    pool_state.set_reserves(
+        total_token_0_amount.checked_sub(token_0_amount).unwrap(),
+        total_token_1_amount.checked_sub(token_1_amount).unwrap(),
+    );
     token_burn(
         ctx.accounts.owner.to_account_info(),
         ctx.accounts.token_program.to_account_info(),
@@ -194,6 +229,7 @@ pub fn withdraw(
         } else {
             ctx.accounts.token_program_2022.to_account_info()
         },
+        ctx.remaining_accounts,
         token_0_amount,
         ctx.accounts.vault_0_mint.decimals,
         &[&[crate::AUTH_SEED.as_bytes(), &[pool_state.auth_bump]]],
@@ -209,11 +245,28 @@ pub fn withdraw(
         } else {
             ctx.accounts.token_program_2022.to_account_info()
         },
+        ctx.remaining_accounts,
         token_1_amount,
         ctx.accounts.vault_1_mint.decimals,
         &[&[crate::AUTH_SEED.as_bytes(), &[pool_state.auth_bump]]],
     )?;
     pool_state.recent_epoch = Clock::get()?.epoch;
 
+    // 调用池的钩子程序，取款总是使用忽略策略
+    invoke_pool_hook(
+        pool_state,
+        &ctx.accounts.owner.to_account_info(),
+        PoolHookAction {
+            pool_id,
+            user: ctx.accounts.owner.key(),
+            kind: PoolHookActionKind::Withdraw as u8,
+            trade_direction: 0,
+            token_0_amount,
+            token_1_amount,
+            lp_amount: lp_token_amount,
+        },
+        ctx.remaining_accounts,
+    )?;
+
     Ok(())
 }
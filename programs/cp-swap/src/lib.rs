@@ -88,12 +88,82 @@ pub mod raydium_cp_swap {
     /// * `fund_fee_rate`- The new fund fee rate of amm config, be set when `param` is 2
     /// * `new_owner`- The config's new owner, be set when `param` is 3
     /// * `new_fund_owner`- The config's new fund owner, be set when `param` is 4
-    /// * `param`- The value can be 0 | 1 | 2 | 3 | 4, otherwise will report a error
+    /// * `create_pool_fee`- The fee for create a new pool, be set when `param` is 5
+    /// * `disable_create_pool`- Disable create pool if value is not 0, be set when `param` is 6
+    /// * `lock_lp_amount`- The lp amount locked when a pool is created, denominated in 9 decimals and scaled to
+    ///   the lp mint decimals like the initial liquidity, be set when `param` is 7
+    /// * `lp_mint_decimals`- The decimals of lp mint of new pools, 1 to 9, be set when `param` is 8
+    /// * `referral_fee_rate`- The rate of referral fee within protocol fee, be set when `param` is 9
+    /// * `max_price_impact_bps`- The max price impact of a swap in basis points, be set when `param` is 10
+    /// * `max_direction_fee_rate`- The max trade fee rate of a swap direction of pools, be set when `param` is 11
+    /// * `param`- The value can be 0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9 | 10 | 11, otherwise will report a error
     ///
     pub fn update_amm_config(ctx: Context<UpdateAmmConfig>, param: u8, value: u64) -> Result<()> {
         instructions::update_amm_config(ctx, param, value)
     }
 
+    /// Create the mint policy which decides the token_2022 mints can be used to create pool
+    /// Must be called by the admin
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The accounts needed by instruction.
+    /// * `allowed_extensions` - Bitwise representation of the allowed token_2022 extensions.
+    ///
+    pub fn create_mint_policy(
+        ctx: Context<CreateMintPolicy>,
+        allowed_extensions: u64,
+    ) -> Result<()> {
+        instructions::create_mint_policy(ctx, allowed_extensions)
+    }
+
+    /// Update the mint policy, the mints to add or remove are passed by remaining accounts
+    /// Must be called by the admin
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `param`- The value can be 0 | 1 | 2 | 3 | 4 | 5 | 6, otherwise will report a error
+    ///   * 0: set the allowed extensions bitmap to `value`
+    ///   * 1 | 2: add | remove the remaining accounts to | from the allow list of mints
+    ///   * 3 | 4: add | remove the remaining accounts to | from the deny list of mints
+    ///   * 5 | 6: add | remove the remaining accounts to | from the transfer hook programs
+    /// * `value`- The allowed extensions bitmap when `param` is 0, must be 0 otherwise
+    ///
+    pub fn update_mint_policy(ctx: Context<UpdateMintPolicy>, param: u8, value: u64) -> Result<()> {
+        instructions::update_mint_policy(ctx, param, value)
+    }
+
+    /// Create the config of the metadata created for the lp mint of new pools
+    /// Must be called by the admin
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The accounts needed by instruction.
+    /// * `uri_template` - The uri of lp mint metadata, `{pool}` and `{lp_mint}` are replaced by the addresses.
+    ///
+    pub fn create_lp_metadata_config(
+        ctx: Context<CreateLpMetadataConfig>,
+        uri_template: String,
+    ) -> Result<()> {
+        instructions::create_lp_metadata_config(ctx, uri_template)
+    }
+
+    /// Update the uri template of the lp mint metadata, only affects pools created later
+    /// Must be called by the admin
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `uri_template` - The new uri template.
+    ///
+    pub fn update_lp_metadata_config(
+        ctx: Context<UpdateLpMetadataConfig>,
+        uri_template: String,
+    ) -> Result<()> {
+        instructions::update_lp_metadata_config(ctx, uri_template)
+    }
+
     /// Update pool status for given value
     ///
     /// # Arguments
@@ -105,6 +175,23 @@ pub mod raydium_cp_swap {
         instructions::update_pool_status(ctx, status)
     }
 
+    /// Limit the amount of each vault can leave the pool by swaps and withdraws within a rolling
+    /// window of slots, the limit and the recent outflows are kept in the pool state
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `max_outflow_bps` - Max outflow of each vault in basis points of the vault amount, 0 means no limit
+    /// * `window` - Slots of the rolling window
+    ///
+    pub fn update_outflow_limit(
+        ctx: Context<UpdateOutflowLimit>,
+        max_outflow_bps: u16,
+        window: u16,
+    ) -> Result<()> {
+        instructions::update_outflow_limit(ctx, max_outflow_bps, window)
+    }
+
     /// Collect the protocol fee accrued to the pool
     ///
     /// # Arguments
@@ -113,15 +200,15 @@ pub mod raydium_cp_swap {
     /// * `amount_0_requested` - The maximum amount of token_0 to send, can be 0 to collect fees in only token_1
     /// * `amount_1_requested` - The maximum amount of token_1 to send, can be 0 to collect fees in only token_0
     ///
-    pub fn collect_protocol_fee(
-        ctx: Context<CollectProtocolFee>,
+    pub fn collect_protocol_fee<'info>(
+        ctx: Context<'_, '_, '_, 'info, CollectProtocolFee<'info>>,
         amount_0_requested: u64,
         amount_1_requested: u64,
     ) -> Result<()> {
         instructions::collect_protocol_fee(ctx, amount_0_requested, amount_1_requested)
     }
 
-    /// Collect the fund fee accrued to the pool
+    /// Collect the fund fee accrued to the pool, fails if the amm config has a fee split
     ///
     /// # Arguments
     ///
@@ -129,14 +216,178 @@ pub mod raydium_cp_swap {
     /// * `amount_0_requested` - The maximum amount of token_0 to send, can be 0 to collect fees in only token_1
     /// * `amount_1_requested` - The maximum amount of token_1 to send, can be 0 to collect fees in only token_0
     ///
-    pub fn collect_fund_fee(
-        ctx: Context<CollectFundFee>,
+    pub fn collect_fund_fee<'info>(
+        ctx: Context<'_, '_, '_, 'info, CollectFundFee<'info>>,
         amount_0_requested: u64,
         amount_1_requested: u64,
     ) -> Result<()> {
         instructions::collect_fund_fee(ctx, amount_0_requested, amount_1_requested)
     }
 
+    /// Deposit the protocol fee accrued to the pool as balanced liquidity, and mint the lp tokens
+    /// to the protocol owner's lp token account, the unbalanced remainder is left accrued
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    ///
+    pub fn compound_protocol_fee(ctx: Context<CompoundProtocolFee>) -> Result<()> {
+        instructions::compound_protocol_fee(ctx)
+    }
+
+    /// Collect all the protocol fee accrued to many pools of the amm config
+    /// The remaining accounts are `pool_count` groups of (pool_state, token_0_vault, token_1_vault,
+    /// vault_0_mint, vault_1_mint), followed by the recipient token accounts, one for each mint
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `pool_count` - The number of pools to collect fee from
+    ///
+    pub fn collect_protocol_fee_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, CollectProtocolFeeBatch<'info>>,
+        pool_count: u8,
+    ) -> Result<()> {
+        instructions::collect_protocol_fee_batch(ctx, pool_count)
+    }
+
+    /// Collect all the fund fee accrued to many pools of the amm config, fails if the amm config has a fee split
+    /// The remaining accounts are `pool_count` groups of (pool_state, token_0_vault, token_1_vault,
+    /// vault_0_mint, vault_1_mint), followed by the recipient token accounts, one for each mint
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `pool_count` - The number of pools to collect fee from
+    ///
+    pub fn collect_fund_fee_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, CollectFundFeeBatch<'info>>,
+        pool_count: u8,
+    ) -> Result<()> {
+        instructions::collect_fund_fee_batch(ctx, pool_count)
+    }
+
+    /// Create the fund fee split of the amm config, the recipient wallets are passed by remaining accounts
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `weights` - The weight of each recipient, the share is weight / total weight
+    /// * `dust_recipient_index` - The index of the recipient receiving the rounding dust
+    ///
+    pub fn create_fee_split(
+        ctx: Context<CreateFeeSplit>,
+        weights: Vec<u16>,
+        dust_recipient_index: u8,
+    ) -> Result<()> {
+        instructions::create_fee_split(ctx, weights, dust_recipient_index)
+    }
+
+    /// Replace the recipients of the fund fee split, the recipient wallets are passed by remaining accounts
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `weights` - The weight of each recipient, the share is weight / total weight
+    /// * `dust_recipient_index` - The index of the recipient receiving the rounding dust
+    ///
+    pub fn update_fee_split(
+        ctx: Context<UpdateFeeSplit>,
+        weights: Vec<u16>,
+        dust_recipient_index: u8,
+    ) -> Result<()> {
+        instructions::update_fee_split(ctx, weights, dust_recipient_index)
+    }
+
+    /// Collect all the fund fee accrued to the pool and distribute it pro rata to the fee split recipients
+    /// The remaining accounts are the token_0 and token_1 accounts of the recipients, a recipient
+    /// whose share is 0 can be omitted
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    ///
+    pub fn collect_fund_fee_split<'info>(
+        ctx: Context<'_, '_, '_, 'info, CollectFundFeeSplit<'info>>,
+    ) -> Result<()> {
+        instructions::collect_fund_fee_split(ctx)
+    }
+
+    /// Create the trade fee discount tiers of the amm config, keyed on the amount of the discount
+    /// mint locked by the swap payer with `lock_fee_discount_token`. The swaps pass the fee discount
+    /// and the payer's fee discount lock by remaining accounts to get the discount
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `min_balances` - The min locked amount of the discount mint of each tier, strictly ascending
+    /// * `discount_bps` - The discount of the trade fee rate of each tier in basis points, ascending
+    /// * `min_lock_duration` - Seconds the locked tokens can't be unlocked for after the last lock, not 0
+    ///
+    pub fn create_fee_discount(
+        ctx: Context<CreateFeeDiscount>,
+        min_balances: Vec<u64>,
+        discount_bps: Vec<u16>,
+        min_lock_duration: u64,
+    ) -> Result<()> {
+        instructions::create_fee_discount(ctx, min_balances, discount_bps, min_lock_duration)
+    }
+
+    /// Replace the discount mint and the tiers of the trade fee discount, the tokens locked of the
+    /// previous discount mint no longer count and can be unlocked after the lock duration
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `min_balances` - The min locked amount of the discount mint of each tier, strictly ascending
+    /// * `discount_bps` - The discount of the trade fee rate of each tier in basis points, ascending
+    /// * `min_lock_duration` - Seconds the locked tokens can't be unlocked for after the last lock, not 0
+    ///
+    pub fn update_fee_discount(
+        ctx: Context<UpdateFeeDiscount>,
+        min_balances: Vec<u64>,
+        discount_bps: Vec<u16>,
+        min_lock_duration: u64,
+    ) -> Result<()> {
+        instructions::update_fee_discount(ctx, min_balances, discount_bps, min_lock_duration)
+    }
+
+    /// Lock the discount mint for the trade fee discount, locking more restarts the lock duration
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    /// * `amount` - The amount of the discount mint to lock
+    ///
+    pub fn lock_fee_discount_token<'info>(
+        ctx: Context<'_, '_, '_, 'info, LockFeeDiscountToken<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::lock_fee_discount_token(ctx, amount)
+    }
+
+    /// Unlock all the locked tokens once the lock duration of the fee discount has passed
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    ///
+    pub fn unlock_fee_discount_token<'info>(
+        ctx: Context<'_, '_, '_, 'info, UnlockFeeDiscountToken<'info>>,
+    ) -> Result<()> {
+        instructions::unlock_fee_discount_token(ctx)
+    }
+
+    /// Absorb the tokens sent to the vaults directly into the accounted reserves of the pool
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of accounts
+    ///
+    pub fn sync(ctx: Context<SyncReserves>) -> Result<()> {
+        instructions::sync(ctx)
+    }
+
     /// Creates a pool for the given token pair and the initial price
     ///
     /// # Arguments
@@ -146,8 +397,8 @@ pub mod raydium_cp_swap {
     /// * `init_amount_1` - the initial amount_1 to deposit
     /// * `open_time` - the timestamp allowed for swap
     ///
-    pub fn initialize(
-        ctx: Context<Initialize>,
+    pub fn initialize<'info>(
+        ctx: Context<'_, '_, '_, 'info, Initialize<'info>>,
         init_amount_0: u64,
         init_amount_1: u64,
         open_time: u64,
@@ -155,6 +406,228 @@ pub mod raydium_cp_swap {
         instructions::initialize(ctx, init_amount_0, init_amount_1, open_time)
     }
 
+    /// Send the tokens sent to the vaults directly, which exceed the accounted reserves and fees,
+    /// to the recipient token accounts. The accounted reserves are never changed, so the pool price
+    /// can't be moved by a skim. Pools with no accounted reserves yet have no surplus
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn skim<'info>(ctx: Context<'_, '_, '_, 'info, Skim<'info>>) -> Result<()> {
+        instructions::skim(ctx)
+    }
+
+    /// Write an observation at the current price of the pool, callable by anyone once the
+    /// update duration has passed since the last observation.
+    /// The price feed of the pool is refreshed as well, which must be passed by the remaining accounts once created
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn observe_pool(ctx: Context<ObservePool>) -> Result<()> {
+        instructions::observe_pool(ctx)
+    }
+
+    /// Extend an observation account created before the cumulative price ticks were recorded,
+    /// the twap of the pool is available once the observations after the extension cover the window
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn extend_observation(ctx: Context<ExtendObservation>) -> Result<()> {
+        instructions::extend_observation(ctx)
+    }
+
+    /// Create the price feed of the pool, which is refreshed by the swaps and `observe_pool`.
+    /// Once it's created, they must pass it by the remaining accounts as writable
+    /// Must be called by the pool creator or admin
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `twap_window` - The window of the twap in seconds
+    ///
+    pub fn create_price_feed(ctx: Context<CreatePriceFeed>, twap_window: u64) -> Result<()> {
+        instructions::create_price_feed(ctx, twap_window)
+    }
+
+    /// Set the launch fee of the pool, which decays to the trade fee rate after open time
+    /// Must be called by the pool creator or admin before the pool opens
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `launch_fee_rate` - The trade fee rate when the pool opens, 0 to disable the launch fee
+    /// * `duration` - Seconds after open time the launch fee decays over
+    /// * `mode` - 0: linear decay, 1: exponential decay
+    ///
+    pub fn update_launch_fee(
+        ctx: Context<UpdateLaunchFee>,
+        launch_fee_rate: u64,
+        duration: u32,
+        mode: u8,
+    ) -> Result<()> {
+        instructions::update_launch_fee(ctx, launch_fee_rate, duration, mode)
+    }
+
+    /// Set the trade fee rates of the pool for each swap direction, such as different fees for buys and sells
+    /// Must be called by the pool creator or admin before the pool opens
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `zero_for_one_fee_rate` - The trade fee rate of swaps from token_0 to token_1, 0 to use the trade fee rate of config,
+    ///   at most the `max_direction_fee_rate` of config
+    /// * `one_for_zero_fee_rate` - The trade fee rate of swaps from token_1 to token_0, 0 to use the trade fee rate of config,
+    ///   at most the `max_direction_fee_rate` of config
+    ///
+    pub fn update_direction_fee(
+        ctx: Context<UpdateDirectionFee>,
+        zero_for_one_fee_rate: u64,
+        one_for_zero_fee_rate: u64,
+    ) -> Result<()> {
+        instructions::update_direction_fee(ctx, zero_for_one_fee_rate, one_for_zero_fee_rate)
+    }
+
+    /// Set the quote mint of the pool, the whole trade fee is taken in the quote mint, from the input
+    /// when swapping the quote mint in and from the output when swapping the quote mint out
+    /// Must be called by the pool creator before the pool opens, or by the admin
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `quote_mint` - One of the pool mints, default key to take the trade fee in the input token
+    ///
+    pub fn update_quote_mint(ctx: Context<UpdateQuoteMint>, quote_mint: Pubkey) -> Result<()> {
+        instructions::update_quote_mint(ctx, quote_mint)
+    }
+
+    /// Set the anti-whale window of the pool, which caps the output of each swap after open time
+    /// Must be called by the pool creator or admin before the pool opens
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `duration` - Seconds after open time the swap output is capped
+    /// * `max_output_bps` - Max output of a swap in basis points of the output vault amount, 0 to disable
+    ///
+    pub fn update_anti_whale(
+        ctx: Context<UpdateAntiWhale>,
+        duration: u32,
+        max_output_bps: u16,
+    ) -> Result<()> {
+        instructions::update_anti_whale(ctx, duration, max_output_bps)
+    }
+
+    /// Set the circuit breaker of the pool, which checks the price after each swap against the twap,
+    /// the swaps must pass the circuit breaker account once enabled
+    /// Must be called by the pool creator or admin
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `max_deviation_bps` - Max deviation of the price from the twap in basis points, 0 to disable and lift the pause
+    /// * `twap_window` - Seconds of the twap window
+    /// * `cooldown` - Seconds the swaps are paused for by `trip_circuit_breaker`, at most one day,
+    ///   0 for no pause. A deviating swap is always rejected and never pauses the swaps itself
+    ///
+    pub fn update_circuit_breaker(
+        ctx: Context<UpdateCircuitBreaker>,
+        max_deviation_bps: u16,
+        twap_window: u16,
+        cooldown: u32,
+    ) -> Result<()> {
+        instructions::update_circuit_breaker(ctx, max_deviation_bps, twap_window, cooldown)
+    }
+
+    /// Trip the circuit breaker of the pool, which pauses the swaps for the cooldown
+    /// Must be called by the pool creator or admin, e.g. a keeper watching the pool price
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn trip_circuit_breaker(ctx: Context<TripCircuitBreaker>) -> Result<()> {
+        instructions::trip_circuit_breaker(ctx)
+    }
+
+    /// Set the price guard of the pool, which checks the price after each swap against the
+    /// external reference price account, the swaps must pass both accounts once enabled
+    /// Must be called by the admin, who approves the reference price account
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `max_deviation_bps` - Max deviation of the price from the reference price in basis points, 0 to disable
+    /// * `max_staleness` - Max seconds since the reference price was published
+    ///
+    pub fn update_price_guard(
+        ctx: Context<UpdatePriceGuard>,
+        max_deviation_bps: u16,
+        max_staleness: u32,
+    ) -> Result<()> {
+        instructions::update_price_guard(ctx, max_deviation_bps, max_staleness)
+    }
+
+    /// Register the hook program of the pool, which is called after each swap, deposit and withdraw
+    /// with the `PoolHookAction`, the actions must pass the hook accounts by remaining accounts
+    /// Must be called by the pool creator or admin
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `policy` - 0: no hook, 1: a hook failure is fatal, 2: a hook failure is not fatal where the runtime
+    ///   allows it, i.e. a hook program no longer executable is skipped, while a failed CPI still fails the
+    ///   transaction. The withdraws always use 2
+    ///
+    pub fn update_pool_hook(ctx: Context<UpdatePoolHook>, policy: u8) -> Result<()> {
+        instructions::update_pool_hook(ctx, policy)
+    }
+
+    /// Create the allow list of wallets can swap before the open time of the pool
+    /// Must be called by the pool creator or admin
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    ///
+    pub fn create_pre_open_allow_list(ctx: Context<CreatePreOpenAllowList>) -> Result<()> {
+        instructions::create_pre_open_allow_list(ctx)
+    }
+
+    /// Update the pre-open allow list, the keys to add or remove are passed by remaining accounts
+    /// Must be called by the pool creator or admin
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `keys`- Add remaining accounts to the allow list when `param` is 0, remove when `param` is 1
+    /// * `merkle_root`- The new merkle root of allowed wallets, be set when `param` is 2
+    /// * `param`- The value can be 0 | 1 | 2, otherwise will report a error
+    ///
+    pub fn update_pre_open_allow_list(
+        ctx: Context<UpdatePreOpenAllowList>,
+        param: u8,
+        merkle_root: [u8; 32],
+    ) -> Result<()> {
+        instructions::update_pre_open_allow_list(ctx, param, merkle_root)
+    }
+
+    /// Prove the signer is in the merkle tree of the pre-open allow list and issue a pass,
+    /// which is passed by remaining accounts to swap before the open time
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx`- The context of accounts
+    /// * `proof` - The merkle proof of the signer
+    ///
+    pub fn claim_pre_open_pass(ctx: Context<ClaimPreOpenPass>, proof: Vec<[u8; 32]>) -> Result<()> {
+        instructions::claim_pre_open_pass(ctx, proof)
+    }
+
     /// Deposit lp token to the pool
     ///
     /// # Arguments
@@ -164,8 +637,8 @@ pub mod raydium_cp_swap {
     /// * `maximum_token_0_amount` -  Maximum token 0 amount to deposit, prevents excessive slippage
     /// * `maximum_token_1_amount` - Maximum token 1 amount to deposit, prevents excessive slippage
     ///
-    pub fn deposit(
-        ctx: Context<Deposit>,
+    pub fn deposit<'info>(
+        ctx: Context<'_, '_, '_, 'info, Deposit<'info>>,
         lp_token_amount: u64,
         maximum_token_0_amount: u64,
         maximum_token_1_amount: u64,
@@ -187,8 +660,8 @@ pub mod raydium_cp_swap {
     /// * `minimum_token_0_amount` -  Minimum amount of token 0 to receive, prevents excessive slippage
     /// * `minimum_token_1_amount` -  Minimum amount of token 1 to receive, prevents excessive slippage
     ///
-    pub fn withdraw(
-        ctx: Context<Withdraw>,
+    pub fn withdraw<'info>(
+        ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>,
         lp_token_amount: u64,
         minimum_token_0_amount: u64,
         minimum_token_1_amount: u64,
@@ -202,6 +675,10 @@ pub mod raydium_cp_swap {
     }
 
     /// Swap the tokens in the pool base input amount
+    /// Before the open time, the signer must be in the pool's pre-open allow list, the allow list and
+    /// the signer's pre-open pass are passed by remaining accounts
+    /// The optional referrer token account of the fee token, not owned by the signer, receives
+    /// the `referral_fee_rate` share of the protocol fee
     ///
     /// # Arguments
     ///
@@ -209,8 +686,8 @@ pub mod raydium_cp_swap {
     /// * `amount_in` -  input amount to transfer, output to DESTINATION is based on the exchange rate
     /// * `minimum_amount_out` -  Minimum amount of output token, prevents excessive slippage
     ///
-    pub fn swap_base_input(
-        ctx: Context<Swap>,
+    pub fn swap_base_input<'info>(
+        ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
         amount_in: u64,
         minimum_amount_out: u64,
     ) -> Result<()> {
@@ -218,6 +695,10 @@ pub mod raydium_cp_swap {
     }
 
     /// Swap the tokens in the pool base output amount
+    /// Before the open time, the signer must be in the pool's pre-open allow list, the allow list and
+    /// the signer's pre-open pass are passed by remaining accounts
+    /// The optional referrer token account of the fee token, not owned by the signer, receives
+    /// the `referral_fee_rate` share of the protocol fee
     ///
     /// # Arguments
     ///
@@ -225,7 +706,11 @@ pub mod raydium_cp_swap {
     /// * `max_amount_in` -  input amount prevents excessive slippage
     /// * `amount_out` -  amount of output token
     ///
-    pub fn swap_base_output(ctx: Context<Swap>, max_amount_in: u64, amount_out: u64) -> Result<()> {
+    pub fn swap_base_output<'info>(
+        ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+        max_amount_in: u64,
+        amount_out: u64,
+    ) -> Result<()> {
         instructions::swap_base_output(ctx, max_amount_in, amount_out)
     }
 }
@@ -0,0 +1,212 @@
+use super::oracle::{self, ObservationState, ObservationTicks};
+use super::pool::{PoolGuardBitIndex, PoolState};
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+
+pub const CIRCUIT_BREAKER_SEED: &str = "circuit_breaker";
+/// Max seconds the swaps can be paused for by tripping the circuit breaker
+pub const MAX_CIRCUIT_BREAKER_COOLDOWN: u32 = 86400;
+
+/// Checks the price after each swap of the pool against the twap of the observations
+#[account]
+#[derive(Default, Debug)]
+pub struct CircuitBreaker {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The pool the circuit breaker belongs
+    pub pool_id: Pubkey,
+    /// Max deviation in basis points of the price after a swap from the twap, 0 means no limit
+    pub max_deviation_bps: u16,
+    /// Seconds of the twap window the price after a swap is checked against
+    pub twap_window: u16,
+    /// Seconds the swaps are paused for once the circuit breaker is tripped,
+    /// at most `MAX_CIRCUIT_BREAKER_COOLDOWN`
+    pub cooldown: u32,
+    /// The swaps are paused by the circuit breaker until the timestamp, 0 means not paused.
+    /// It's kept apart from the pool status, so the status set by admin is never changed by it
+    pub paused_until: u64,
+    /// padding for future updates
+    pub padding: [u64; 8],
+}
+
+impl CircuitBreaker {
+    pub const LEN: usize = 8 + 1 + 32 + 2 + 2 + 4 + 8 + 8 * 8;
+
+    /// Disabling the circuit breaker also lifts the pause
+    pub fn set(&mut self, max_deviation_bps: u16, twap_window: u16, cooldown: u32) {
+        self.max_deviation_bps = max_deviation_bps;
+        self.twap_window = twap_window;
+        self.cooldown = cooldown;
+        if max_deviation_bps == 0 {
+            self.paused_until = 0;
+        }
+    }
+
+    /// Whether the swaps are paused by the circuit breaker at the block timestamp
+    pub fn paused(&self, block_timestamp: u64) -> bool {
+        block_timestamp < self.paused_until
+    }
+
+    /// Pause the swaps for the cooldown from the block timestamp. A swap never trips the circuit
+    /// breaker itself, or anyone could pause the pool with a single price moving swap
+    pub fn trip(&mut self, block_timestamp: u64) -> Result<u64> {
+        // the swaps only check the pause while the circuit breaker is enabled
+        require_gt!(self.max_deviation_bps, 0, ErrorCode::NotApproved);
+        require_gt!(self.cooldown, 0, ErrorCode::InvalidInput);
+        self.paused_until = block_timestamp.saturating_add(u64::from(self.cooldown));
+        Ok(self.paused_until)
+    }
+
+    /// Check the price tick of the reserves after a swap against the twap of the observations,
+    /// the swap is rejected if the deviation exceeds the limit. Nothing is checked without
+    /// enough observations
+    pub fn check(
+        &self,
+        observation_state: &ObservationState,
+        observation_ticks: Option<&ObservationTicks>,
+        tick: i32,
+    ) -> Result<()> {
+        if self.max_deviation_bps == 0 {
+            return Ok(());
+        }
+        if let Some(twap_tick) =
+            observation_state.get_twap_tick(observation_ticks, u64::from(self.twap_window))
+        {
+            require_gte!(
+                u64::from(self.max_deviation_bps),
+                oracle::tick_deviation_bps(tick, twap_tick),
+                ErrorCode::ExceededTwapDeviation
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Find the circuit breaker of the pool if it's enabled, which must be passed by the remaining
+/// accounts
+pub fn find_circuit_breaker(
+    pool_id: Pubkey,
+    pool_state: &PoolState,
+    remaining_accounts: &[AccountInfo],
+) -> Result<Option<CircuitBreaker>> {
+    if !pool_state.get_guard_by_bit(PoolGuardBitIndex::CircuitBreaker) {
+        return Ok(None);
+    }
+    let circuit_breaker = remaining_accounts
+        .iter()
+        .filter(|account_info| account_info.owner == &crate::id())
+        .find_map(|account_info| {
+            let data = account_info.try_borrow_data().ok()?;
+            CircuitBreaker::try_deserialize(&mut &data[..])
+                .ok()
+                .filter(|circuit_breaker| circuit_breaker.pool_id == pool_id)
+        })
+        .ok_or(ErrorCode::MissingCircuitBreakerAccount)?;
+    Ok(Some(circuit_breaker))
+}
+
+#[cfg(test)]
+pub mod circuit_breaker_test {
+    use super::*;
+
+    #[test]
+    fn check_circuit_breaker_test() {
+        let mut observation_state = ObservationState::default();
+        let mut observation_ticks = ObservationTicks::default();
+        observation_state.update(Some(&mut observation_ticks), 1000, 0, 0, 0);
+        observation_state.update(Some(&mut observation_ticks), 1060, 0, 0, 0);
+        let tick_at = |reserve_1: u64| oracle::tick_at_reserves(1_000_000, reserve_1);
+
+        let mut circuit_breaker = CircuitBreaker {
+            pool_id: Pubkey::new_unique(),
+            ..CircuitBreaker::default()
+        };
+        let check = |circuit_breaker: &CircuitBreaker, reserve_1: u64| {
+            circuit_breaker.check(
+                &observation_state,
+                Some(&observation_ticks),
+                tick_at(reserve_1),
+            )
+        };
+        // disabled
+        assert!(check(&circuit_breaker, 1_100_000).is_ok());
+
+        // rejected, and a deviating swap never pauses the swaps
+        circuit_breaker.set(500, 60, 300);
+        assert!(check(&circuit_breaker, 1_100_000).is_err());
+        assert!(check(&circuit_breaker, 900_000).is_err());
+        assert!(check(&circuit_breaker, 1_040_000).is_ok());
+        assert_eq!(circuit_breaker.paused_until, 0);
+        // not enough observations for the window
+        circuit_breaker.set(500, 120, 300);
+        assert!(check(&circuit_breaker, 1_100_000).is_ok());
+    }
+
+    #[test]
+    fn trip_circuit_breaker_test() {
+        let mut circuit_breaker = CircuitBreaker::default();
+        // disabled
+        assert!(circuit_breaker.trip(1060).is_err());
+        // no cooldown
+        circuit_breaker.set(500, 60, 0);
+        assert!(circuit_breaker.trip(1060).is_err());
+        assert!(!circuit_breaker.paused(1060));
+
+        // the swaps are paused for the cooldown
+        circuit_breaker.set(500, 60, 300);
+        assert_eq!(circuit_breaker.trip(1060).unwrap(), 1360);
+        assert!(circuit_breaker.paused(1359));
+        assert!(!circuit_breaker.paused(1360));
+        // updating the limits keeps the pause, disabling lifts it
+        circuit_breaker.set(1000, 60, 300);
+        assert!(circuit_breaker.paused(1359));
+        circuit_breaker.set(0, 0, 0);
+        assert!(!circuit_breaker.paused(1359));
+    }
+
+    #[test]
+    fn find_circuit_breaker_test() {
+        let pool_id = Pubkey::new_unique();
+        let owner = crate::id();
+        let mut pool_state = PoolState::default();
+
+        let circuit_breaker_key = Pubkey::new_unique();
+        let mut lamports = 0u64;
+        let mut data = vec![];
+        CircuitBreaker {
+            pool_id,
+            max_deviation_bps: 500,
+            ..CircuitBreaker::default()
+        }
+        .try_serialize(&mut data)
+        .unwrap();
+        let circuit_breaker_info = AccountInfo::new(
+            &circuit_breaker_key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        let remaining_accounts = [circuit_breaker_info];
+
+        // disabled
+        assert!(find_circuit_breaker(pool_id, &pool_state, &[])
+            .unwrap()
+            .is_none());
+
+        // required once enabled
+        pool_state.set_guard_by_bit(PoolGuardBitIndex::CircuitBreaker, true);
+        assert!(find_circuit_breaker(pool_id, &pool_state, &[]).is_err());
+        let circuit_breaker = find_circuit_breaker(pool_id, &pool_state, &remaining_accounts)
+            .unwrap()
+            .unwrap();
+        assert_eq!(circuit_breaker.max_deviation_bps, 500);
+        // the circuit breaker of another pool
+        assert!(
+            find_circuit_breaker(Pubkey::new_unique(), &pool_state, &remaining_accounts).is_err()
+        );
+    }
+}
@@ -1,6 +1,16 @@
+use super::pool::BPS_DENOMINATOR;
+use crate::error::ErrorCode;
+use crate::utils::U128;
 use anchor_lang::prelude::*;
 
 pub const AMM_CONFIG_SEED: &str = "amm_config";
+/// The lp amount locked forever when a pool is created, if it is not set in config
+pub const DEFAULT_LOCK_LP_AMOUNT: u64 = 100;
+/// The decimals of lp mint, if it is not set in config. The initial liquidity
+/// `sqrt(amount_0 * amount_1)` and the locked lp amount of config are denominated in it
+pub const DEFAULT_LP_MINT_DECIMALS: u8 = 9;
+/// Max decimals of lp mint can be set in config
+pub const MAX_LP_MINT_DECIMALS: u8 = 9;
 
 /// 持有工厂现任所有者
 #[account]
@@ -24,10 +34,215 @@ pub struct AmmConfig {
     pub protocol_owner: Pubkey,
     /// Address of the fund fee owner
     pub fund_owner: Pubkey,
+    /// The lp amount locked forever when a pool is created, 0 means `DEFAULT_LOCK_LP_AMOUNT`.
+    /// It's denominated in `DEFAULT_LP_MINT_DECIMALS` and scaled to `lp_mint_decimals`
+    pub lock_lp_amount: u64,
+    /// The decimals of lp mint of new pools, 0 means `DEFAULT_LP_MINT_DECIMALS`
+    pub lp_mint_decimals: u8,
+    pub padding1: [u8; 7],
+    /// The referral fee paid to the referrer of a swap, denominated in hundredths of a bip (10^-6)
+    /// of the protocol fee
+    pub referral_fee_rate: u64,
+    /// Max price impact of a swap in basis points, 0 means no limit
+    pub max_price_impact_bps: u64,
+    /// Max trade fee rate of a swap direction the pool creators can set, denominated in
+    /// hundredths of a bip (10^-6), 0 means `trade_fee_rate`
+    pub max_direction_fee_rate: u64,
     /// padding
-    pub padding: [u64; 16],
+    pub padding: [u64; 11],
 }
 
 impl AmmConfig {
-    pub const LEN: usize = 8 + 1 + 1 + 2 + 4 * 8 + 32 * 2 + 8 * 16;
+    pub const LEN: usize = 8 + 1 + 1 + 2 + 4 * 8 + 32 * 2 + 8 + 1 + 7 + 8 + 8 + 8 + 8 * 11;
+
+    pub fn get_lock_lp_amount(&self) -> u64 {
+        if self.lock_lp_amount == 0 {
+            DEFAULT_LOCK_LP_AMOUNT
+        } else {
+            self.lock_lp_amount
+        }
+    }
+
+    pub fn get_lp_mint_decimals(&self) -> u8 {
+        if self.lp_mint_decimals == 0 {
+            DEFAULT_LP_MINT_DECIMALS
+        } else {
+            self.lp_mint_decimals
+        }
+    }
+
+    /// Scale an lp amount denominated in `DEFAULT_LP_MINT_DECIMALS` to the lp mint decimals,
+    /// so an lp token is worth the same share of the reserves whatever decimals the lp mint has
+    pub fn scale_lp_amount(&self, amount: u64, round_up: bool) -> u64 {
+        // the lp mint decimals never exceed `DEFAULT_LP_MINT_DECIMALS`
+        let divisor = 10u64.pow(u32::from(
+            DEFAULT_LP_MINT_DECIMALS.saturating_sub(self.get_lp_mint_decimals()),
+        ));
+        if round_up {
+            amount.div_ceil(divisor)
+        } else {
+            amount / divisor
+        }
+    }
+
+    /// Get the initial lp amount of a pool and the part of it locked forever, scaled to the
+    /// lp mint decimals. The locked amount is rounded up, so it's never scaled down to zero
+    pub fn initial_lp_amounts(&self, amount_0: u64, amount_1: u64) -> (u64, u64) {
+        let liquidity = U128::from(amount_0)
+            .checked_mul(amount_1.into())
+            .unwrap()
+            .integer_sqrt()
+            .as_u64();
+        (
+            self.scale_lp_amount(liquidity, false),
+            self.scale_lp_amount(self.get_lock_lp_amount(), true),
+        )
+    }
+
+    pub fn get_max_direction_fee_rate(&self) -> u64 {
+        if self.max_direction_fee_rate == 0 {
+            self.trade_fee_rate
+        } else {
+            self.max_direction_fee_rate
+        }
+    }
+
+    /// Reject the direction fee rate above `max_direction_fee_rate`, so a pool can't charge
+    /// one side of the trades a prohibitive fee
+    pub fn check_direction_fee_rate(&self, direction_fee_rate: u64) -> Result<()> {
+        require_gte!(
+            self.get_max_direction_fee_rate(),
+            direction_fee_rate,
+            ErrorCode::ExceededMaxDirectionFeeRate
+        );
+        Ok(())
+    }
+
+    /// Reject the swap if its price impact exceeds `max_price_impact_bps`
+    pub fn check_price_impact(&self, price_impact_bps: u64) -> Result<()> {
+        if self.max_price_impact_bps != 0 && price_impact_bps > self.max_price_impact_bps {
+            return err!(ErrorCode::ExceededMaxPriceImpact);
+        }
+        Ok(())
+    }
+}
+
+/// Get the price impact of a swap in basis points from the prices of `PoolState::token_price_x32`
+/// before and after it, the higher price of the two tokens is compared for precision
+pub fn price_impact_bps(price_before_x32: (u128, u128), price_after_x32: (u128, u128)) -> u64 {
+    let (price_before_x32, price_after_x32) = if price_before_x32.0 >= price_before_x32.1 {
+        (price_before_x32.0, price_after_x32.0)
+    } else {
+        (price_before_x32.1, price_after_x32.1)
+    };
+    if price_before_x32 == 0 {
+        return 0;
+    }
+    let price_impact_bps = price_before_x32
+        .abs_diff(price_after_x32)
+        .saturating_mul(u128::from(BPS_DENOMINATOR))
+        / price_before_x32;
+    u64::try_from(price_impact_bps).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+pub mod config_test {
+    use super::*;
+
+    #[test]
+    fn config_len_test() {
+        let amm_config = AmmConfig::default();
+        assert_eq!(amm_config.try_to_vec().unwrap().len(), AmmConfig::LEN - 8)
+    }
+
+    #[test]
+    fn price_impact_test() {
+        let q32 = 1u128 << 32;
+        assert_eq!(price_impact_bps((q32, q32), (q32, q32)), 0);
+        // the price of token_0 falls from 4 to 3.6
+        assert_eq!(
+            price_impact_bps((4 * q32, q32 / 4), (36 * q32 / 10, q32 * 10 / 36)),
+            1000
+        );
+        // the price of token_1 rises from 4 to 5
+        assert_eq!(
+            price_impact_bps((q32 / 4, 4 * q32), (q32 / 5, 5 * q32)),
+            2500
+        );
+
+        let mut amm_config = AmmConfig::default();
+        assert!(amm_config.check_price_impact(10_000).is_ok());
+        amm_config.max_price_impact_bps = 1000;
+        assert!(amm_config.check_price_impact(1000).is_ok());
+        assert!(amm_config.check_price_impact(1001).is_err());
+    }
+
+    #[test]
+    fn check_direction_fee_rate_test() {
+        let mut amm_config = AmmConfig {
+            trade_fee_rate: 2500,
+            ..AmmConfig::default()
+        };
+        // capped by the trade fee rate by default
+        assert!(amm_config.check_direction_fee_rate(2500).is_ok());
+        assert!(amm_config.check_direction_fee_rate(2501).is_err());
+
+        amm_config.max_direction_fee_rate = 10_000;
+        assert!(amm_config.check_direction_fee_rate(10_000).is_ok());
+        assert!(amm_config.check_direction_fee_rate(10_001).is_err());
+        assert!(amm_config.check_direction_fee_rate(999_999).is_err());
+    }
+
+    #[test]
+    fn lock_lp_amount_and_lp_mint_decimals_test() {
+        let mut amm_config = AmmConfig::default();
+        assert_eq!(amm_config.get_lock_lp_amount(), DEFAULT_LOCK_LP_AMOUNT);
+        assert_eq!(amm_config.get_lp_mint_decimals(), DEFAULT_LP_MINT_DECIMALS);
+
+        amm_config.lock_lp_amount = 1_000_000;
+        amm_config.lp_mint_decimals = 6;
+        assert_eq!(amm_config.get_lock_lp_amount(), 1_000_000);
+        assert_eq!(amm_config.get_lp_mint_decimals(), 6);
+    }
+
+    #[test]
+    fn initial_lp_amounts_test() {
+        // the default decimals keep sqrt(amount_0 * amount_1)
+        let amm_config = AmmConfig::default();
+        assert_eq!(
+            amm_config.initial_lp_amounts(4_000_000_000, 9_000_000_000),
+            (6_000_000_000, DEFAULT_LOCK_LP_AMOUNT)
+        );
+
+        // 6 decimals mint a thousandth of the raw amounts, the same share of the reserves
+        let amm_config = AmmConfig {
+            lp_mint_decimals: 6,
+            ..AmmConfig::default()
+        };
+        assert_eq!(
+            amm_config.initial_lp_amounts(4_000_000_000, 9_000_000_000),
+            (6_000_000, 1)
+        );
+        let amm_config = AmmConfig {
+            lp_mint_decimals: 6,
+            lock_lp_amount: 1_000_000,
+            ..AmmConfig::default()
+        };
+        assert_eq!(
+            amm_config.initial_lp_amounts(4_000_000_000, 9_000_000_000),
+            (6_000_000, 1_000)
+        );
+        assert_eq!(amm_config.scale_lp_amount(1_999, false), 1);
+        assert_eq!(amm_config.scale_lp_amount(1_001, true), 2);
+
+        // the locked lp amount is at least 1 with the fewest decimals
+        let amm_config = AmmConfig {
+            lp_mint_decimals: 1,
+            ..AmmConfig::default()
+        };
+        assert_eq!(
+            amm_config.initial_lp_amounts(4_000_000_000, 9_000_000_000),
+            (60, 1)
+        );
+    }
 }
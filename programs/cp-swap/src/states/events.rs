@@ -36,4 +36,25 @@ pub struct SwapEvent {
     pub input_transfer_fee: u64,
     pub output_transfer_fee: u64,
     pub base_input: bool,
+    /// trade fee rate applied to the swap direction
+    pub trade_fee_rate: u64,
+    /// trade fee in input token, or in output token if `fee_on_output`
+    pub trade_fee: u64,
+    /// the trade fee is taken in output token, which is the quote mint of the pool
+    pub fee_on_output: bool,
+    /// owner of the referrer token account, default key if there is no referrer
+    pub referrer: Pubkey,
+    /// referral fee paid to the referrer in the same token as the trade fee
+    pub referral_fee: u64,
+}
+
+/// Emitted when the swaps of a pool are paused by tripping the circuit breaker
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct CircuitBreakerEvent {
+    pub pool_id: Pubkey,
+    /// the pool creator or admin who tripped the circuit breaker
+    pub authority: Pubkey,
+    /// the swaps are paused until the timestamp
+    pub paused_until: u64,
 }
@@ -0,0 +1,351 @@
+use super::pool::BPS_DENOMINATOR;
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+
+pub const FEE_DISCOUNT_SEED: &str = "fee_discount";
+pub const FEE_DISCOUNT_LOCK_SEED: &str = "fee_discount_lock";
+pub const FEE_DISCOUNT_VAULT_SEED: &str = "fee_discount_vault";
+// Max number of tiers of a FeeDiscount
+pub const FEE_DISCOUNT_TIER_NUM: usize = 4;
+
+/// Discount tiers of the trade fee rate of an amm config, keyed on the amount of the discount
+/// mint locked by the swap payer in its `FeeDiscountLock`
+#[account]
+#[derive(Default, Debug)]
+pub struct FeeDiscount {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The amm config the fee discount belongs
+    pub amm_config: Pubkey,
+    /// The mint the swap signer locks to get the discount
+    pub discount_mint: Pubkey,
+    /// Number of tiers in use
+    pub tier_count: u8,
+    /// Min locked amount of the discount mint of each tier, strictly ascending
+    pub min_balances: [u64; FEE_DISCOUNT_TIER_NUM],
+    /// Discount of the trade fee rate of each tier in basis points, ascending
+    pub discount_bps: [u16; FEE_DISCOUNT_TIER_NUM],
+    /// Seconds the locked tokens can't be unlocked for after the last lock, never 0,
+    /// so a borrowed balance can't be locked and repaid within a transaction
+    pub min_lock_duration: u64,
+    /// padding for future updates
+    pub padding: [u64; 7],
+}
+
+impl FeeDiscount {
+    pub const LEN: usize =
+        8 + 1 + 32 + 32 + 1 + 8 * FEE_DISCOUNT_TIER_NUM + 2 * FEE_DISCOUNT_TIER_NUM + 8 + 8 * 7;
+
+    pub fn set_tiers(
+        &mut self,
+        discount_mint: Pubkey,
+        min_balances: &[u64],
+        discount_bps: &[u16],
+        min_lock_duration: u64,
+    ) -> Result<()> {
+        require!(
+            !min_balances.is_empty()
+                && min_balances.len() <= FEE_DISCOUNT_TIER_NUM
+                && min_balances.len() == discount_bps.len(),
+            ErrorCode::InvalidInput
+        );
+        require!(
+            min_balances.windows(2).all(|w| w[0] < w[1])
+                && discount_bps.windows(2).all(|w| w[0] <= w[1]),
+            ErrorCode::InvalidInput
+        );
+        require!(
+            discount_bps
+                .iter()
+                .all(|bps| u64::from(*bps) <= BPS_DENOMINATOR),
+            ErrorCode::InvalidInput
+        );
+        require_gt!(min_lock_duration, 0, ErrorCode::InvalidInput);
+        self.discount_mint = discount_mint;
+        self.tier_count = min_balances.len() as u8;
+        self.min_balances = [0u64; FEE_DISCOUNT_TIER_NUM];
+        self.min_balances[..min_balances.len()].copy_from_slice(min_balances);
+        self.discount_bps = [0u16; FEE_DISCOUNT_TIER_NUM];
+        self.discount_bps[..discount_bps.len()].copy_from_slice(discount_bps);
+        self.min_lock_duration = min_lock_duration;
+        Ok(())
+    }
+
+    /// Get the discount in basis points of the highest tier the balance reaches
+    pub fn discount_bps(&self, balance: u64) -> u16 {
+        let count = usize::from(self.tier_count);
+        self.min_balances[..count]
+            .iter()
+            .zip(self.discount_bps[..count].iter())
+            .rev()
+            .find(|(min_balance, _)| balance >= **min_balance)
+            .map_or(0, |(_, discount_bps)| *discount_bps)
+    }
+
+    /// Apply the discount in basis points to the trade fee rate
+    pub fn discounted_fee_rate(trade_fee_rate: u64, discount_bps: u16) -> u64 {
+        trade_fee_rate
+            - (u128::from(trade_fee_rate) * u128::from(discount_bps) / u128::from(BPS_DENOMINATOR))
+                as u64
+    }
+}
+
+/// The discount mint locked by a wallet for the fee discount, the tokens are held by the
+/// `FEE_DISCOUNT_VAULT_SEED` vault of the fee discount and the mint
+#[account]
+#[derive(Default, Debug)]
+pub struct FeeDiscountLock {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The fee discount the tokens are locked for
+    pub fee_discount: Pubkey,
+    /// The wallet locked the tokens
+    pub owner: Pubkey,
+    /// The mint of the locked tokens, the discount mint when they were locked
+    pub mint: Pubkey,
+    /// The locked amount
+    pub amount: u64,
+    /// The timestamp of the last lock, which restarts the lock duration
+    pub locked_at: u64,
+    /// padding for future updates
+    pub padding: [u64; 4],
+}
+
+impl FeeDiscountLock {
+    pub const LEN: usize = 8 + 1 + 32 * 3 + 8 + 8 + 8 * 4;
+
+    /// Lock more tokens of the mint, the lock duration restarts from the block timestamp
+    pub fn lock(&mut self, mint: Pubkey, amount: u64, block_timestamp: u64) -> Result<()> {
+        // the tokens of the previous discount mint must be unlocked first
+        if self.amount != 0 {
+            require_keys_eq!(self.mint, mint, ErrorCode::InvalidInput);
+        }
+        self.mint = mint;
+        self.amount = self.amount.checked_add(amount).unwrap();
+        self.locked_at = block_timestamp;
+        Ok(())
+    }
+
+    /// Unlock all the tokens once the lock duration has passed, return the unlocked amount
+    pub fn unlock(&mut self, min_lock_duration: u64, block_timestamp: u64) -> Result<u64> {
+        require_gte!(
+            block_timestamp,
+            self.locked_at.saturating_add(min_lock_duration),
+            ErrorCode::FeeDiscountLocked
+        );
+        let amount = self.amount;
+        self.amount = 0;
+        Ok(amount)
+    }
+}
+
+/// Get the trade fee rate discounted by the fee discount of the amm config. The fee discount and
+/// the `FeeDiscountLock` of the swap payer are passed by the remaining accounts, the trade fee
+/// rate is not discounted without them. Only the tokens of the current discount mint locked
+/// for the fee discount count, and they can't be unlocked within the lock duration, so a
+/// borrowed balance never qualifies
+pub fn get_discounted_trade_fee_rate(
+    amm_config: Pubkey,
+    payer: &Pubkey,
+    trade_fee_rate: u64,
+    remaining_accounts: &[AccountInfo],
+) -> u64 {
+    let program_accounts = || {
+        remaining_accounts
+            .iter()
+            .filter(|account_info| account_info.owner == &crate::id())
+    };
+    let (fee_discount_key, fee_discount) = match program_accounts().find_map(|account_info| {
+        let data = account_info.try_borrow_data().ok()?;
+        FeeDiscount::try_deserialize(&mut &data[..])
+            .ok()
+            .filter(|fee_discount| fee_discount.amm_config == amm_config)
+            .map(|fee_discount| (account_info.key, fee_discount))
+    }) {
+        Some(fee_discount) => fee_discount,
+        None => return trade_fee_rate,
+    };
+    let locked_amount = program_accounts()
+        .find_map(|account_info| {
+            let data = account_info.try_borrow_data().ok()?;
+            FeeDiscountLock::try_deserialize(&mut &data[..])
+                .ok()
+                .filter(|fee_discount_lock| {
+                    fee_discount_lock.fee_discount == *fee_discount_key
+                        && fee_discount_lock.owner == *payer
+                        && fee_discount_lock.mint == fee_discount.discount_mint
+                })
+                .map(|fee_discount_lock| fee_discount_lock.amount)
+        })
+        .unwrap_or(0);
+    FeeDiscount::discounted_fee_rate(trade_fee_rate, fee_discount.discount_bps(locked_amount))
+}
+
+#[cfg(test)]
+pub mod fee_discount_test {
+    use super::*;
+
+    #[test]
+    fn fee_discount_len_test() {
+        let fee_discount = FeeDiscount::default();
+        assert_eq!(
+            fee_discount.try_to_vec().unwrap().len(),
+            FeeDiscount::LEN - 8
+        );
+        let fee_discount_lock = FeeDiscountLock::default();
+        assert_eq!(
+            fee_discount_lock.try_to_vec().unwrap().len(),
+            FeeDiscountLock::LEN - 8
+        );
+    }
+
+    #[test]
+    fn discount_tiers_test() {
+        let mut fee_discount = FeeDiscount::default();
+        let discount_mint = Pubkey::new_unique();
+        fee_discount
+            .set_tiers(
+                discount_mint,
+                &[1_000, 10_000, 100_000],
+                &[500, 1000, 2500],
+                86400,
+            )
+            .unwrap();
+        assert_eq!(fee_discount.discount_bps(0), 0);
+        assert_eq!(fee_discount.discount_bps(999), 0);
+        assert_eq!(fee_discount.discount_bps(1_000), 500);
+        assert_eq!(fee_discount.discount_bps(99_999), 1000);
+        assert_eq!(fee_discount.discount_bps(u64::MAX), 2500);
+
+        assert_eq!(FeeDiscount::discounted_fee_rate(2500, 0), 2500);
+        assert_eq!(FeeDiscount::discounted_fee_rate(2500, 1000), 2250);
+        assert_eq!(FeeDiscount::discounted_fee_rate(2500, 10000), 0);
+        assert_eq!(FeeDiscount::discounted_fee_rate(2501, 5000), 1251);
+
+        // invalid tiers
+        assert!(fee_discount.set_tiers(discount_mint, &[], &[], 1).is_err());
+        assert!(fee_discount
+            .set_tiers(discount_mint, &[1, 2], &[100], 1)
+            .is_err());
+        assert!(fee_discount
+            .set_tiers(discount_mint, &[2, 1], &[100, 200], 1)
+            .is_err());
+        assert!(fee_discount
+            .set_tiers(discount_mint, &[1, 2], &[200, 100], 1)
+            .is_err());
+        assert!(fee_discount
+            .set_tiers(discount_mint, &[1], &[10001], 1)
+            .is_err());
+        assert!(fee_discount
+            .set_tiers(discount_mint, &[1, 2, 3, 4, 5], &[1, 2, 3, 4, 5], 1)
+            .is_err());
+        // the locked tokens must not be unlockable within the transaction
+        assert!(fee_discount
+            .set_tiers(discount_mint, &[1], &[100], 0)
+            .is_err());
+    }
+
+    #[test]
+    fn lock_and_unlock_test() {
+        let discount_mint = Pubkey::new_unique();
+        let mut fee_discount_lock = FeeDiscountLock::default();
+        fee_discount_lock.lock(discount_mint, 1_000, 1000).unwrap();
+        // locked within the same transaction, it can't be unlocked to repay a loan
+        assert!(fee_discount_lock.unlock(86400, 1000).is_err());
+        assert!(fee_discount_lock.unlock(86400, 87399).is_err());
+
+        // locking more restarts the lock duration
+        fee_discount_lock.lock(discount_mint, 500, 2000).unwrap();
+        assert_eq!(fee_discount_lock.amount, 1_500);
+        assert!(fee_discount_lock.unlock(86400, 87400).is_err());
+        assert_eq!(fee_discount_lock.unlock(86400, 88400).unwrap(), 1_500);
+        assert_eq!(fee_discount_lock.amount, 0);
+
+        // another mint only after the tokens are unlocked
+        let other_mint = Pubkey::new_unique();
+        fee_discount_lock.lock(discount_mint, 1_000, 3000).unwrap();
+        assert!(fee_discount_lock.lock(other_mint, 1_000, 3000).is_err());
+        assert_eq!(fee_discount_lock.unlock(86400, 89400).unwrap(), 1_000);
+        fee_discount_lock.lock(other_mint, 1_000, 89400).unwrap();
+        assert_eq!(fee_discount_lock.mint, other_mint);
+    }
+
+    #[test]
+    fn discounted_trade_fee_rate_test() {
+        let amm_config = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let discount_mint = Pubkey::new_unique();
+        let owner = crate::id();
+
+        let mut fee_discount = FeeDiscount {
+            amm_config,
+            ..FeeDiscount::default()
+        };
+        fee_discount
+            .set_tiers(discount_mint, &[1_000], &[1000], 86400)
+            .unwrap();
+        let fee_discount_key = Pubkey::new_unique();
+        let mut fee_discount_lamports = 0u64;
+        let mut fee_discount_data = vec![];
+        fee_discount.try_serialize(&mut fee_discount_data).unwrap();
+        let fee_discount_info = AccountInfo::new(
+            &fee_discount_key,
+            false,
+            false,
+            &mut fee_discount_lamports,
+            &mut fee_discount_data,
+            &owner,
+            false,
+            0,
+        );
+
+        let lock_data = |fee_discount: Pubkey, owner: Pubkey, mint: Pubkey| {
+            let mut data = vec![];
+            FeeDiscountLock {
+                fee_discount,
+                owner,
+                mint,
+                amount: 1_000,
+                ..FeeDiscountLock::default()
+            }
+            .try_serialize(&mut data)
+            .unwrap();
+            data
+        };
+        let lock_keys = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let mut lock_lamports = [0u64; 4];
+        let mut lock_data = [
+            lock_data(fee_discount_key, payer, discount_mint),
+            // locked by another wallet
+            lock_data(fee_discount_key, Pubkey::new_unique(), discount_mint),
+            // locked for another fee discount
+            lock_data(Pubkey::new_unique(), payer, discount_mint),
+            // locked tokens of a previous discount mint
+            lock_data(fee_discount_key, payer, Pubkey::new_unique()),
+        ];
+        let [payer_lock, other_owner_lock, other_fee_discount_lock, other_mint_lock] = lock_keys
+            .iter()
+            .zip(lock_lamports.iter_mut())
+            .zip(lock_data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, false, lamports, data, &owner, false, 0)
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+
+        let discounted = |remaining_accounts: &[AccountInfo]| {
+            get_discounted_trade_fee_rate(amm_config, &payer, 2500, remaining_accounts)
+        };
+        assert_eq!(discounted(std::slice::from_ref(&payer_lock)), 2500);
+        assert_eq!(discounted(&[fee_discount_info.clone(), payer_lock]), 2250);
+        for lock in [other_owner_lock, other_fee_discount_lock, other_mint_lock] {
+            assert_eq!(discounted(&[fee_discount_info.clone(), lock]), 2500);
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+
+pub const FEE_SPLIT_SEED: &str = "fee_split";
+// Max number of recipients of a FeeSplit
+pub const FEE_SPLIT_RECIPIENT_NUM: usize = 8;
+
+/// Split of the fund fee of an amm config among weighted recipients
+#[account]
+#[derive(Default, Debug)]
+pub struct FeeSplit {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The amm config the fee split belongs
+    pub amm_config: Pubkey,
+    /// Number of recipients in use
+    pub recipient_count: u8,
+    /// Wallets receiving the fund fee, the first `recipient_count` are in use
+    pub recipients: [Pubkey; FEE_SPLIT_RECIPIENT_NUM],
+    /// Weight of each recipient, the share is weight / total weight
+    pub weights: [u16; FEE_SPLIT_RECIPIENT_NUM],
+    /// Index of the recipient receiving the rounding dust
+    pub dust_recipient_index: u8,
+    /// padding for future updates
+    pub padding: [u64; 8],
+}
+
+impl FeeSplit {
+    pub const LEN: usize =
+        8 + 1 + 32 + 1 + 32 * FEE_SPLIT_RECIPIENT_NUM + 2 * FEE_SPLIT_RECIPIENT_NUM + 1 + 8 * 8;
+
+    pub fn set_recipients(
+        &mut self,
+        recipients: &[Pubkey],
+        weights: &[u16],
+        dust_recipient_index: u8,
+    ) -> Result<()> {
+        require!(
+            !recipients.is_empty()
+                && recipients.len() <= FEE_SPLIT_RECIPIENT_NUM
+                && recipients.len() == weights.len(),
+            ErrorCode::InvalidInput
+        );
+        require!(
+            weights.iter().all(|weight| *weight > 0),
+            ErrorCode::InvalidInput
+        );
+        require_gt!(
+            recipients.len(),
+            usize::from(dust_recipient_index),
+            ErrorCode::InvalidInput
+        );
+        self.recipient_count = recipients.len() as u8;
+        self.recipients = [Pubkey::default(); FEE_SPLIT_RECIPIENT_NUM];
+        self.recipients[..recipients.len()].copy_from_slice(recipients);
+        self.weights = [0u16; FEE_SPLIT_RECIPIENT_NUM];
+        self.weights[..weights.len()].copy_from_slice(weights);
+        self.dust_recipient_index = dust_recipient_index;
+        Ok(())
+    }
+
+    /// Split the amount pro rata by weights, the rounding dust goes to the dust recipient
+    pub fn split(&self, amount: u64) -> Vec<u64> {
+        let count = usize::from(self.recipient_count);
+        let total_weight: u128 = self.weights[..count].iter().map(|w| u128::from(*w)).sum();
+        let mut shares: Vec<u64> = self.weights[..count]
+            .iter()
+            .map(|weight| (u128::from(amount) * u128::from(*weight) / total_weight) as u64)
+            .collect();
+        let dust = amount - shares.iter().sum::<u64>();
+        shares[usize::from(self.dust_recipient_index)] += dust;
+        shares
+    }
+}
+
+#[cfg(test)]
+pub mod fee_split_test {
+    use super::*;
+
+    #[test]
+    fn fee_split_len_test() {
+        let fee_split = FeeSplit::default();
+        assert_eq!(fee_split.try_to_vec().unwrap().len(), FeeSplit::LEN - 8)
+    }
+
+    #[test]
+    fn split_test() {
+        let mut fee_split = FeeSplit::default();
+        let recipients: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        fee_split
+            .set_recipients(&recipients, &[1, 1, 1], 2)
+            .unwrap();
+        assert_eq!(fee_split.split(100), vec![33, 33, 34]);
+        assert_eq!(fee_split.split(0), vec![0, 0, 0]);
+
+        fee_split
+            .set_recipients(&recipients[..2], &[7000, 3000], 0)
+            .unwrap();
+        assert_eq!(fee_split.split(1001), vec![701, 300]);
+        assert_eq!(fee_split.split(u64::MAX).iter().sum::<u64>(), u64::MAX);
+
+        // invalid recipients
+        assert!(fee_split.set_recipients(&[], &[], 0).is_err());
+        assert!(fee_split.set_recipients(&recipients, &[1, 1], 0).is_err());
+        assert!(fee_split
+            .set_recipients(&recipients, &[1, 0, 1], 0)
+            .is_err());
+        assert!(fee_split
+            .set_recipients(&recipients, &[1, 1, 1], 3)
+            .is_err());
+    }
+}
@@ -0,0 +1,119 @@
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::mpl_token_metadata::{
+    MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH,
+};
+
+pub const LP_METADATA_CONFIG_SEED: &str = "lp_metadata_config";
+/// Placeholder in the uri template replaced by the pool address
+pub const URI_POOL_PLACEHOLDER: &str = "{pool}";
+/// Placeholder in the uri template replaced by the lp mint address
+pub const URI_LP_MINT_PLACEHOLDER: &str = "{lp_mint}";
+/// Suffix of the lp mint name, such as "SOL-USDC CP-LP"
+pub const LP_NAME_SUFFIX: &str = " CP-LP";
+/// Max chars of each token symbol used in the lp mint symbol
+const LP_SYMBOL_TOKEN_CHARS: usize = (MAX_SYMBOL_LENGTH - 1) / 2;
+/// Max length of a base58 encoded pubkey
+const MAX_PUBKEY_STR_LENGTH: usize = 44;
+
+/// Admin managed config of the metadata created for the lp mint of new pools
+#[account]
+#[derive(Default, Debug)]
+pub struct LpMetadataConfig {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The uri of lp mint metadata, `{pool}` and `{lp_mint}` are replaced by the addresses
+    pub uri_template: String,
+    /// padding for future updates
+    pub padding: [u64; 16],
+}
+
+impl LpMetadataConfig {
+    pub const LEN: usize = 8 + 1 + 4 + MAX_URI_LENGTH + 8 * 16;
+
+    pub fn set_uri_template(&mut self, uri_template: String) -> Result<()> {
+        let placeholders = uri_template.matches(URI_POOL_PLACEHOLDER).count()
+            + uri_template.matches(URI_LP_MINT_PLACEHOLDER).count();
+        // the rendered uri must always fit in the metadata account
+        let max_len = uri_template.len() + placeholders * MAX_PUBKEY_STR_LENGTH;
+        require_gte!(MAX_URI_LENGTH, max_len, ErrorCode::InvalidInput);
+        self.uri_template = uri_template;
+        Ok(())
+    }
+
+    pub fn render_uri(&self, pool_id: &Pubkey, lp_mint: &Pubkey) -> String {
+        self.uri_template
+            .replace(URI_POOL_PLACEHOLDER, &pool_id.to_string())
+            .replace(URI_LP_MINT_PLACEHOLDER, &lp_mint.to_string())
+    }
+}
+
+/// Build the lp mint name and symbol from the symbols of the pool tokens,
+/// such as ("SOL-USDC CP-LP", "SOL-USDC")
+pub fn lp_name_and_symbol(token_0_symbol: &str, token_1_symbol: &str) -> (String, String) {
+    let pair = format!("{}-{}", token_0_symbol, token_1_symbol);
+    let name = format!(
+        "{}{}",
+        truncate_chars(&pair, MAX_NAME_LENGTH - LP_NAME_SUFFIX.len()),
+        LP_NAME_SUFFIX
+    );
+    let symbol = format!(
+        "{}-{}",
+        truncate_chars(token_0_symbol, LP_SYMBOL_TOKEN_CHARS),
+        truncate_chars(token_1_symbol, LP_SYMBOL_TOKEN_CHARS)
+    );
+    (name, symbol)
+}
+
+/// Truncate the string to at most `max_bytes` bytes without splitting a char
+fn truncate_chars(s: &str, max_bytes: usize) -> &str {
+    if s.len() <= max_bytes {
+        return s;
+    }
+    let mut end = max_bytes;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+#[cfg(test)]
+pub mod lp_metadata_test {
+    use super::*;
+
+    #[test]
+    fn lp_name_and_symbol_test() {
+        let (name, symbol) = lp_name_and_symbol("SOL", "USDC");
+        assert_eq!(name, "SOL-USDC CP-LP");
+        assert_eq!(symbol, "SOL-USDC");
+
+        let (name, symbol) = lp_name_and_symbol("ABCDEFGHIJKLMNOP", "QRSTUVWXYZ0123456789");
+        assert_eq!(name.len(), MAX_NAME_LENGTH);
+        assert!(name.ends_with(LP_NAME_SUFFIX));
+        assert_eq!(symbol, "ABCD-QRST");
+        assert!(symbol.len() <= MAX_SYMBOL_LENGTH);
+
+        // never split a multi-byte char
+        let (name, symbol) = lp_name_and_symbol("ÅÅÅ", "ÖÖÖ");
+        assert_eq!(symbol, "ÅÅ-ÖÖ");
+        assert_eq!(name, "ÅÅÅ-ÖÖÖ CP-LP");
+    }
+
+    #[test]
+    fn uri_template_test() {
+        let mut config = LpMetadataConfig::default();
+        let pool_id = Pubkey::new_unique();
+        let lp_mint = Pubkey::new_unique();
+        config
+            .set_uri_template("https://example.com/lp/{pool}/{lp_mint}.json".to_string())
+            .unwrap();
+        assert_eq!(
+            config.render_uri(&pool_id, &lp_mint),
+            format!("https://example.com/lp/{}/{}.json", pool_id, lp_mint)
+        );
+
+        let too_long = format!("https://example.com/{}/{{pool}}", "a".repeat(150));
+        assert!(config.set_uri_template(too_long).is_err());
+        assert!(config.uri_template.starts_with("https://example.com/lp/"));
+    }
+}
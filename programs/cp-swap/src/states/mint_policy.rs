@@ -0,0 +1,226 @@
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+use spl_token_2022::extension::ExtensionType;
+
+pub const MINT_POLICY_SEED: &str = "mint_policy";
+// Number of entries in each mint list of MintPolicy
+pub const MINT_POLICY_LIST_NUM: usize = 64;
+// Number of transfer hook programs can be allowed by MintPolicy
+pub const MINT_POLICY_HOOK_PROGRAM_NUM: usize = 16;
+
+/// Admin managed policy to decide which token_2022 mints can be used to create pool
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct MintPolicy {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// Bitwise representation of the allowed token_2022 extensions,
+    /// bit n enables the `ExtensionType` whose discriminant is n
+    pub allowed_extensions: u64,
+    /// Mints which are allowed whatever extensions they have, empty slot is Pubkey::default()
+    pub allow_mints: [Pubkey; MINT_POLICY_LIST_NUM],
+    /// Mints which are never allowed, checked before everything else
+    pub deny_mints: [Pubkey; MINT_POLICY_LIST_NUM],
+    /// Transfer hook programs which mints with the TransferHook extension can use
+    pub hook_programs: [Pubkey; MINT_POLICY_HOOK_PROGRAM_NUM],
+    /// padding for future updates
+    pub padding: [u64; 64],
+}
+
+impl Default for MintPolicy {
+    #[inline]
+    fn default() -> MintPolicy {
+        MintPolicy {
+            bump: 0,
+            allowed_extensions: 0,
+            allow_mints: [Pubkey::default(); MINT_POLICY_LIST_NUM],
+            deny_mints: [Pubkey::default(); MINT_POLICY_LIST_NUM],
+            hook_programs: [Pubkey::default(); MINT_POLICY_HOOK_PROGRAM_NUM],
+            padding: [0u64; 64],
+        }
+    }
+}
+
+impl MintPolicy {
+    pub const LEN: usize =
+        8 + 1 + 8 + 32 * MINT_POLICY_LIST_NUM * 2 + 32 * MINT_POLICY_HOOK_PROGRAM_NUM + 8 * 64;
+
+    /// The extensions supported before the policy account was introduced
+    pub fn default_allowed_extensions() -> u64 {
+        [
+            ExtensionType::TransferFeeConfig,
+            ExtensionType::MetadataPointer,
+            ExtensionType::TokenMetadata,
+            ExtensionType::InterestBearingConfig,
+            ExtensionType::ScaledUiAmount,
+        ]
+        .iter()
+        .fold(0, |flags, e| flags | Self::extension_flag(*e))
+    }
+
+    pub fn extension_flag(extension: ExtensionType) -> u64 {
+        let index = u16::from(extension);
+        if index >= u64::BITS as u16 {
+            0
+        } else {
+            1u64 << index
+        }
+    }
+
+    pub fn is_extension_allowed(&self, extension: ExtensionType) -> bool {
+        let flag = Self::extension_flag(extension);
+        flag != 0 && self.allowed_extensions & flag == flag
+    }
+
+    pub fn is_allowed_mint(&self, mint: &Pubkey) -> bool {
+        *mint != Pubkey::default() && self.allow_mints.contains(mint)
+    }
+
+    pub fn is_denied_mint(&self, mint: &Pubkey) -> bool {
+        *mint != Pubkey::default() && self.deny_mints.contains(mint)
+    }
+
+    pub fn is_allowed_hook_program(&self, program_id: &Pubkey) -> bool {
+        *program_id != Pubkey::default() && self.hook_programs.contains(program_id)
+    }
+
+    pub fn add_allow_mint(&mut self, mint: Pubkey) -> Result<()> {
+        insert_key(&mut self.allow_mints, mint, ErrorCode::MintPolicyListFull)
+    }
+
+    pub fn remove_allow_mint(&mut self, mint: Pubkey) -> Result<()> {
+        remove_key(&mut self.allow_mints, mint)
+    }
+
+    pub fn add_deny_mint(&mut self, mint: Pubkey) -> Result<()> {
+        insert_key(&mut self.deny_mints, mint, ErrorCode::MintPolicyListFull)
+    }
+
+    pub fn remove_deny_mint(&mut self, mint: Pubkey) -> Result<()> {
+        remove_key(&mut self.deny_mints, mint)
+    }
+
+    pub fn add_hook_program(&mut self, program_id: Pubkey) -> Result<()> {
+        insert_key(
+            &mut self.hook_programs,
+            program_id,
+            ErrorCode::MintPolicyListFull,
+        )
+    }
+
+    pub fn remove_hook_program(&mut self, program_id: Pubkey) -> Result<()> {
+        remove_key(&mut self.hook_programs, program_id)
+    }
+}
+
+/// Insert the key into an empty slot of the list, return `full_error` if there is no empty slot
+pub(crate) fn insert_key(list: &mut [Pubkey], key: Pubkey, full_error: ErrorCode) -> Result<()> {
+    require_keys_neq!(key, Pubkey::default());
+    if list.contains(&key) {
+        return Ok(());
+    }
+    let slot = list
+        .iter_mut()
+        .find(|k| **k == Pubkey::default())
+        .ok_or(full_error)?;
+    *slot = key;
+    Ok(())
+}
+
+/// Remove the key from the list, its slot is reset to the default key
+pub(crate) fn remove_key(list: &mut [Pubkey], key: Pubkey) -> Result<()> {
+    let slot = list
+        .iter_mut()
+        .find(|k| **k == key)
+        .ok_or(ErrorCode::InvalidInput)?;
+    *slot = Pubkey::default();
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod mint_policy_test {
+    use super::*;
+
+    #[test]
+    fn mint_policy_size_test() {
+        assert_eq!(std::mem::size_of::<MintPolicy>(), MintPolicy::LEN - 8)
+    }
+
+    #[test]
+    fn extension_flags_test() {
+        let mut mint_policy = MintPolicy::default();
+        assert!(!mint_policy.is_extension_allowed(ExtensionType::TransferFeeConfig));
+
+        mint_policy.allowed_extensions = MintPolicy::default_allowed_extensions();
+        assert!(mint_policy.is_extension_allowed(ExtensionType::TransferFeeConfig));
+        assert!(mint_policy.is_extension_allowed(ExtensionType::MetadataPointer));
+        assert!(mint_policy.is_extension_allowed(ExtensionType::TokenMetadata));
+        assert!(mint_policy.is_extension_allowed(ExtensionType::InterestBearingConfig));
+        assert!(mint_policy.is_extension_allowed(ExtensionType::ScaledUiAmount));
+        assert!(!mint_policy.is_extension_allowed(ExtensionType::PermanentDelegate));
+        assert!(!mint_policy.is_extension_allowed(ExtensionType::TransferHook));
+
+        mint_policy.allowed_extensions |= MintPolicy::extension_flag(ExtensionType::TransferHook);
+        assert!(mint_policy.is_extension_allowed(ExtensionType::TransferHook));
+    }
+
+    #[test]
+    fn allow_and_deny_mints_test() {
+        let mut mint_policy = MintPolicy::default();
+        let mint = Pubkey::new_unique();
+        assert!(!mint_policy.is_allowed_mint(&mint));
+        assert!(!mint_policy.is_allowed_mint(&Pubkey::default()));
+
+        mint_policy.add_allow_mint(mint).unwrap();
+        // add twice, nothing to change
+        mint_policy.add_allow_mint(mint).unwrap();
+        assert!(mint_policy.is_allowed_mint(&mint));
+        assert_eq!(
+            mint_policy
+                .allow_mints
+                .iter()
+                .filter(|k| **k == mint)
+                .count(),
+            1
+        );
+        assert!(!mint_policy.is_denied_mint(&mint));
+
+        mint_policy.add_deny_mint(mint).unwrap();
+        assert!(mint_policy.is_denied_mint(&mint));
+
+        mint_policy.remove_allow_mint(mint).unwrap();
+        assert!(!mint_policy.is_allowed_mint(&mint));
+        assert!(mint_policy.remove_allow_mint(mint).is_err());
+
+        mint_policy.remove_deny_mint(mint).unwrap();
+        assert!(!mint_policy.is_denied_mint(&mint));
+    }
+
+    #[test]
+    fn hook_programs_test() {
+        let mut mint_policy = MintPolicy::default();
+        let program_id = Pubkey::new_unique();
+        assert!(!mint_policy.is_allowed_hook_program(&program_id));
+
+        mint_policy.add_hook_program(program_id).unwrap();
+        assert!(mint_policy.is_allowed_hook_program(&program_id));
+        assert!(!mint_policy.is_allowed_mint(&program_id));
+
+        mint_policy.remove_hook_program(program_id).unwrap();
+        assert!(!mint_policy.is_allowed_hook_program(&program_id));
+        for _ in 0..MINT_POLICY_HOOK_PROGRAM_NUM {
+            mint_policy.add_hook_program(Pubkey::new_unique()).unwrap();
+        }
+        assert!(mint_policy.add_hook_program(Pubkey::new_unique()).is_err());
+    }
+
+    #[test]
+    fn mint_list_full_test() {
+        let mut mint_policy = MintPolicy::default();
+        for _ in 0..MINT_POLICY_LIST_NUM {
+            mint_policy.add_allow_mint(Pubkey::new_unique()).unwrap();
+        }
+        assert!(mint_policy.add_allow_mint(Pubkey::new_unique()).is_err());
+    }
+}
@@ -9,3 +9,30 @@ pub use events::*;
 
 pub mod oracle;
 pub use oracle::*;
+
+pub mod mint_policy;
+pub use mint_policy::*;
+
+pub mod lp_metadata;
+pub use lp_metadata::*;
+
+pub mod pre_open_allow_list;
+pub use pre_open_allow_list::*;
+
+pub mod fee_split;
+pub use fee_split::*;
+
+pub mod price_feed;
+pub use price_feed::*;
+
+pub mod price_guard;
+pub use price_guard::*;
+
+pub mod circuit_breaker;
+pub use circuit_breaker::*;
+
+pub mod pool_hook;
+pub use pool_hook::*;
+
+pub mod fee_discount;
+pub use fee_discount::*;
@@ -1,6 +1,9 @@
 /// Oracle provides price data useful for a wide variety of system designs
 ///
+use super::pool::BPS_DENOMINATOR;
+use crate::utils::U256;
 use anchor_lang::prelude::*;
+use std::cell::RefMut;
 #[cfg(test)]
 use std::time::{SystemTime, UNIX_EPOCH};
 /// Seed to derive account address and signature
@@ -8,6 +11,12 @@ pub const OBSERVATION_SEED: &str = "observation";
 // Number of ObservationState element
 pub const OBSERVATION_NUM: usize = 100;
 pub const OBSERVATION_UPDATE_DURATION_DEFAULT: u64 = 15;
+/// The max absolute tick, the price of token_0 in token_1 is 1.0001^tick and within [2^-64, 2^64]
+pub const MAX_TICK: i32 = 443636;
+/// log2(1.0001) in Q0.64
+const LOG2_1_0001_X64: i128 = 2661169563308229;
+/// 1.0001 in Q64.64
+const ONE_0001_X64: u128 = 18448588748116922571;
 
 /// The element of observations in ObservationState
 #[zero_copy(unsafe)]
@@ -53,6 +62,79 @@ impl Default for ObservationState {
     }
 }
 
+/// The cumulative price ticks of the observations, stored behind the ObservationState in the
+/// observation account. Accounts created before it must be extended by `extend_observation`
+#[zero_copy(unsafe)]
+#[repr(C, packed)]
+#[derive(bytemuck::Pod, bytemuck::Zeroable)]
+pub struct ObservationTicks {
+    /// The block timestamp of the first observation with the cumulative tick,
+    /// the earlier observations are ignored by the twap
+    pub start_timestamp: u64,
+    /// The cumulative of the price tick of token_0 of the observation at the same index,
+    /// the price of token_0 in token_1 is 1.0001^tick
+    pub cumulative_ticks: [i64; OBSERVATION_NUM],
+    /// padding for feature update
+    pub padding: [u64; 4],
+}
+
+impl Default for ObservationTicks {
+    #[inline]
+    fn default() -> ObservationTicks {
+        ObservationTicks {
+            start_timestamp: 0,
+            cumulative_ticks: [0i64; OBSERVATION_NUM],
+            padding: [0u64; 4],
+        }
+    }
+}
+
+impl ObservationTicks {
+    pub const LEN: usize = 8 + 8 * OBSERVATION_NUM + 8 * 4;
+
+    /// Read the cumulative ticks from the data of an observation account, None if the account
+    /// is not extended yet
+    pub fn try_from_account_data(data: &[u8]) -> Option<ObservationTicks> {
+        let start = ObservationState::LEN;
+        let data = data.get(start..start + ObservationTicks::LEN)?;
+        Some(*bytemuck::from_bytes::<ObservationTicks>(data))
+    }
+}
+
+/// Load the observation state for write, with the cumulative ticks behind it if the account
+/// has been extended
+pub fn load_observation_mut<'a>(
+    observation_state: &'a AccountLoader<ObservationState>,
+) -> Result<(
+    RefMut<'a, ObservationState>,
+    Option<RefMut<'a, ObservationTicks>>,
+)> {
+    let account_info: &AccountInfo = observation_state.as_ref();
+    if !account_info.is_writable {
+        return Err(anchor_lang::error::ErrorCode::AccountNotMutable.into());
+    }
+    let data = account_info.try_borrow_mut_data()?;
+    // the discriminator is checked when the accounts are deserialized
+    let data = RefMut::map(data, |data| &mut data[8..]);
+    let state_len = std::mem::size_of::<ObservationState>();
+    let ticks_len = std::mem::size_of::<ObservationTicks>();
+    if data.len() < state_len + ticks_len {
+        return Ok((
+            RefMut::map(data, |data| {
+                bytemuck::from_bytes_mut(&mut data[..state_len])
+            }),
+            None,
+        ));
+    }
+    let (state, ticks) = RefMut::map_split(data, |data| data.split_at_mut(state_len));
+    Ok((
+        RefMut::map(state, bytemuck::from_bytes_mut),
+        Some(RefMut::map(ticks, |data| {
+            bytemuck::from_bytes_mut(&mut data[..ticks_len])
+        })),
+    ))
+}
+
 impl ObservationState {
     pub const LEN: usize = 8 + 1 + 2 + 32 + (Observation::LEN * OBSERVATION_NUM) + 8 * 4;
 
@@ -63,9 +145,11 @@ impl ObservationState {
     /// # Arguments
     ///
     /// * `self` - The ObservationState account to write in
+    /// * `observation_ticks` - The cumulative ticks to write in, None if the account is not extended
     /// * `block_timestamp` - The current timestamp of to update
     /// * `token_0_price_x32` - The token_0_price_x32 at the time of the new observation
     /// * `token_1_price_x32` - The token_1_price_x32 at the time of the new observation
+    /// * `tick` - The price tick of token_0 at the time of the new observation
     /// * `observation_index` - The last update index of element in the oracle array
     ///
     /// # Return
@@ -73,9 +157,11 @@ impl ObservationState {
     ///
     pub fn update(
         &mut self,
+        observation_ticks: Option<&mut ObservationTicks>,
         block_timestamp: u64,
         token_0_price_x32: u128,
         token_1_price_x32: u128,
+        tick: i32,
     ) {
         let observation_index = self.observation_index;
         if !self.initialized {
@@ -84,6 +170,10 @@ impl ObservationState {
             self.observations[observation_index as usize].block_timestamp = block_timestamp;
             self.observations[observation_index as usize].cumulative_token_0_price_x32 = 0;
             self.observations[observation_index as usize].cumulative_token_1_price_x32 = 0;
+            if let Some(observation_ticks) = observation_ticks {
+                observation_ticks.start_timestamp = block_timestamp;
+                observation_ticks.cumulative_ticks[observation_index as usize] = 0;
+            }
         } else {
             let last_observation = self.observations[observation_index as usize];
             let delta_time = block_timestamp.saturating_sub(last_observation.block_timestamp);
@@ -107,9 +197,124 @@ impl ObservationState {
                 last_observation
                     .cumulative_token_1_price_x32
                     .wrapping_add(delta_token_1_price_x32);
+            if let Some(observation_ticks) = observation_ticks {
+                // the first observation since the account is extended starts from the last one
+                if observation_ticks.start_timestamp == 0 {
+                    observation_ticks.start_timestamp = last_observation.block_timestamp;
+                    observation_ticks.cumulative_ticks[observation_index as usize] = 0;
+                }
+                observation_ticks.cumulative_ticks[next_observation_index as usize] =
+                    observation_ticks.cumulative_ticks[observation_index as usize]
+                        .wrapping_add(i64::from(tick) * delta_time as i64);
+            }
             self.observation_index = next_observation_index;
         }
     }
+
+    /// Get the geometric mean price tick of token_0 over at least `window` seconds ending at the
+    /// latest observation, return None if the observations with the cumulative ticks don't cover the window
+    pub fn get_twap_tick(
+        &self,
+        observation_ticks: Option<&ObservationTicks>,
+        window: u64,
+    ) -> Option<i32> {
+        let observation_ticks = observation_ticks?;
+        if !self.initialized || observation_ticks.start_timestamp == 0 {
+            return None;
+        }
+        let latest_index = self.observation_index as usize;
+        let mut index = latest_index;
+        let latest_observation = self.observations[index];
+        let target_timestamp = latest_observation.block_timestamp.checked_sub(window)?;
+        for _ in 0..OBSERVATION_NUM - 1 {
+            index = if index == 0 {
+                OBSERVATION_NUM - 1
+            } else {
+                index - 1
+            };
+            let observation = self.observations[index];
+            // not written yet or written without the cumulative tick
+            if observation.block_timestamp < observation_ticks.start_timestamp {
+                return None;
+            }
+            if observation.block_timestamp <= target_timestamp {
+                let delta_time = latest_observation.block_timestamp - observation.block_timestamp;
+                let delta_tick = observation_ticks.cumulative_ticks[latest_index]
+                    .wrapping_sub(observation_ticks.cumulative_ticks[index]);
+                return Some(delta_tick.div_euclid(delta_time as i64) as i32);
+            }
+        }
+        None
+    }
+}
+
+/// log2 of a non-zero value, Q32.32
+fn log2_x32(value: u128) -> i64 {
+    let msb = 127 - value.leading_zeros();
+    // normalize to [1, 2) with 63 fractional bits
+    let mut x = if msb >= 63 {
+        value >> (msb - 63)
+    } else {
+        value << (63 - msb)
+    };
+    let mut log2 = i64::from(msb) << 32;
+    let mut bit = 1i64 << 31;
+    while bit > 0 {
+        x = (x * x) >> 63;
+        if x >= 1u128 << 64 {
+            x >>= 1;
+            log2 += bit;
+        }
+        bit >>= 1;
+    }
+    log2
+}
+
+/// Get the price tick of token_0 in token_1 from the reserves, the price is 1.0001^tick
+pub fn tick_at_reserves(reserve_0: u64, reserve_1: u64) -> i32 {
+    if reserve_0 == 0 || reserve_1 == 0 {
+        return 0;
+    }
+    let log2_price_x32 =
+        i128::from(log2_x32(u128::from(reserve_1)) - log2_x32(u128::from(reserve_0)));
+    let tick = (log2_price_x32 << 32).div_euclid(LOG2_1_0001_X64);
+    tick.clamp(i128::from(-MAX_TICK), i128::from(MAX_TICK)) as i32
+}
+
+/// Get the deviation in basis points of the price at `tick` from the price at `base_tick`
+pub fn tick_deviation_bps(tick: i32, base_tick: i32) -> u64 {
+    let ratio_x64 = price_x64_at_tick(tick.saturating_sub(base_tick));
+    let deviation_bps =
+        (U256::from(ratio_x64.abs_diff(1u128 << 64)) * U256::from(BPS_DENOMINATOR)) >> 64;
+    if deviation_bps > U256::from(u64::MAX) {
+        u64::MAX
+    } else {
+        deviation_bps.as_u64()
+    }
+}
+
+/// Get the price of token_0 in token_1 at the tick, Q64.64, saturated at u128::MAX
+pub fn price_x64_at_tick(tick: i32) -> u128 {
+    let mut exponent = tick.clamp(-MAX_TICK, MAX_TICK).unsigned_abs();
+    let mut price_x64 = U256::from(1u128 << 64);
+    let mut base_x64 = U256::from(ONE_0001_X64);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            price_x64 = (price_x64 * base_x64) >> 64;
+        }
+        exponent >>= 1;
+        if exponent > 0 {
+            base_x64 = (base_x64 * base_x64) >> 64;
+        }
+    }
+    if tick < 0 {
+        price_x64 = (U256::from(1u128 << 64) << 64) / price_x64;
+    }
+    if price_x64 > U256::from(u128::MAX) {
+        u128::MAX
+    } else {
+        price_x64.as_u128()
+    }
 }
 
 /// Returns the block timestamp truncated to 32 bits, i.e. mod 2**32
@@ -137,4 +342,170 @@ pub mod observation_test {
             ObservationState::LEN - 8
         )
     }
+
+    #[test]
+    fn tick_at_reserves_test() {
+        assert_eq!(tick_at_reserves(1000, 1000), 0);
+        // 1.0001^6931 < 2 < 1.0001^6932
+        assert_eq!(tick_at_reserves(1000, 2000), 6931);
+        assert_eq!(tick_at_reserves(2000, 1000), -6932);
+        assert_eq!(tick_at_reserves(1, u64::MAX), 443636);
+        assert_eq!(tick_at_reserves(u64::MAX, 1), -MAX_TICK);
+    }
+
+    #[test]
+    fn price_x64_at_tick_test() {
+        let q64 = 1u128 << 64;
+        assert_eq!(price_x64_at_tick(0), q64);
+        for tick in [-200_000, -6932, -1, 1, 6931, 200_000] {
+            let price = price_x64_at_tick(tick) as f64 / q64 as f64;
+            let expected = 1.0001f64.powi(tick);
+            assert!((price - expected).abs() / expected < 1e-9);
+            // the tick of the price is the tick itself
+            assert!(
+                tick_at_reserves(1 << 20, ((1u128 << 20) as f64 * price * 1.00001) as u64) >= tick
+            );
+        }
+        assert!(price_x64_at_tick(MAX_TICK) > q64 * (u64::MAX as u128 / 2));
+    }
+
+    #[test]
+    fn tick_deviation_bps_test() {
+        assert_eq!(tick_deviation_bps(100, 100), 0);
+        // 1.0001^1000 ~= 1.10517, 1.0001^-1000 ~= 0.90484
+        assert_eq!(tick_deviation_bps(1000, 0), 1051);
+        assert_eq!(tick_deviation_bps(0, 1000), 951);
+        assert_eq!(tick_deviation_bps(MAX_TICK, -MAX_TICK), u64::MAX);
+    }
+
+    #[test]
+    fn observation_ticks_size_test() {
+        assert_eq!(
+            std::mem::size_of::<ObservationTicks>(),
+            ObservationTicks::LEN
+        )
+    }
+
+    #[test]
+    fn twap_tick_test() {
+        let mut observation_state = ObservationState::default();
+        let mut observation_ticks = ObservationTicks::default();
+        assert_eq!(
+            observation_state.get_twap_tick(Some(&observation_ticks), 60),
+            None
+        );
+        observation_state.update(Some(&mut observation_ticks), 1000, 0, 0, 100);
+        for (i, tick) in [100, 100, -300, 500].into_iter().enumerate() {
+            observation_state.update(
+                Some(&mut observation_ticks),
+                1000 + 30 * (i as u64 + 1),
+                0,
+                0,
+                tick,
+            );
+        }
+        // the tick of each 30s interval: 100, 100, -300, 500
+        let twap_tick = |observation_state: &ObservationState,
+                         observation_ticks: &ObservationTicks,
+                         window: u64| {
+            observation_state.get_twap_tick(Some(observation_ticks), window)
+        };
+        assert_eq!(
+            twap_tick(&observation_state, &observation_ticks, 30),
+            Some(500)
+        );
+        assert_eq!(
+            twap_tick(&observation_state, &observation_ticks, 60),
+            Some(100)
+        );
+        assert_eq!(
+            twap_tick(&observation_state, &observation_ticks, 120),
+            Some(100)
+        );
+        assert_eq!(twap_tick(&observation_state, &observation_ticks, 121), None);
+        // rounding towards negative infinity
+        observation_state.update(Some(&mut observation_ticks), 1150, 0, 0, -601);
+        assert_eq!(
+            twap_tick(&observation_state, &observation_ticks, 30),
+            Some(-601)
+        );
+        assert_eq!(
+            twap_tick(&observation_state, &observation_ticks, 60),
+            Some(-51)
+        );
+        // no twap without the cumulative ticks
+        assert_eq!(observation_state.get_twap_tick(None, 30), None);
+    }
+
+    #[test]
+    fn twap_tick_after_extend_test() {
+        let mut observation_state = ObservationState::default();
+        // written before the account is extended
+        for i in 0..5 {
+            observation_state.update(None, 1000 + 30 * i, 0, 0, 100);
+        }
+        let mut observation_ticks = ObservationTicks::default();
+        observation_state.update(Some(&mut observation_ticks), 1150, 0, 0, 200);
+        assert_eq!({ observation_ticks.start_timestamp }, 1120);
+        assert_eq!(
+            observation_state.get_twap_tick(Some(&observation_ticks), 30),
+            Some(200)
+        );
+        // the observations before the extension are ignored
+        assert_eq!(
+            observation_state.get_twap_tick(Some(&observation_ticks), 31),
+            None
+        );
+        observation_state.update(Some(&mut observation_ticks), 1180, 0, 0, -400);
+        assert_eq!(
+            observation_state.get_twap_tick(Some(&observation_ticks), 60),
+            Some(-100)
+        );
+    }
+
+    #[test]
+    fn load_observation_test() {
+        let key = Pubkey::new_unique();
+        let owner = crate::id();
+        for (data_len, extended) in [
+            (ObservationState::LEN, false),
+            (ObservationState::LEN + ObservationTicks::LEN, true),
+        ] {
+            let mut lamports = 0u64;
+            let mut data = vec![0u8; data_len];
+            data[..8].copy_from_slice(ObservationState::DISCRIMINATOR);
+            let account_info = AccountInfo::new(
+                &key,
+                false,
+                true,
+                &mut lamports,
+                &mut data,
+                &owner,
+                false,
+                0,
+            );
+            let observation_loader =
+                AccountLoader::<ObservationState>::try_from(&account_info).unwrap();
+            {
+                let (mut observation_state, mut observation_ticks) =
+                    load_observation_mut(&observation_loader).unwrap();
+                assert_eq!(observation_ticks.is_some(), extended);
+                observation_state.update(observation_ticks.as_deref_mut(), 1000, 1, 1, 100);
+                observation_state.update(observation_ticks.as_deref_mut(), 1030, 1, 1, 100);
+                assert_eq!(
+                    observation_state.get_twap_tick(observation_ticks.as_deref(), 30),
+                    extended.then_some(100)
+                );
+            }
+            // the observations are kept in the same layout
+            let observation_state = observation_loader.load().unwrap();
+            assert_eq!({ observation_state.observation_index }, 1);
+            assert_eq!({ observation_state.observations[1].block_timestamp }, 1030);
+            assert_eq!(
+                ObservationTicks::try_from_account_data(&account_info.try_borrow_data().unwrap())
+                    .map(|observation_ticks| observation_ticks.cumulative_ticks[1]),
+                extended.then_some(3000)
+            );
+        }
+    }
 }
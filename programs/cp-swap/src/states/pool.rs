@@ -1,3 +1,8 @@
+use super::config::DEFAULT_LOCK_LP_AMOUNT;
+use super::oracle::{self, ObservationState, ObservationTicks};
+use crate::curve::{fees::Fees, TradeDirection};
+use crate::error::ErrorCode;
+use crate::utils::U256;
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::Mint;
 use std::ops::{BitAnd, BitOr, BitXor};
@@ -7,6 +12,11 @@ pub const POOL_LP_MINT_SEED: &str = "pool_lp_mint";
 pub const POOL_VAULT_SEED: &str = "pool_vault";
 
 pub const Q32: u128 = (u32::MAX as u128) + 1; // 2^32
+pub const Q64: u128 = (u64::MAX as u128) + 1; // 2^64
+/// Denominator of the values in basis points
+pub const BPS_DENOMINATOR: u64 = 10_000;
+/// Denominator of the outflow rates of the vaults
+pub const OUTFLOW_RATE_DENOMINATOR: u64 = 100_000_000;
 
 pub enum PoolStatusBitIndex {
     Deposit,
@@ -14,12 +24,41 @@ pub enum PoolStatusBitIndex {
     Swap,
 }
 
+/// The optional side accounts of the pool, each is required by the swaps once enabled,
+/// the hook also by the deposits and withdraws
+pub enum PoolGuardBitIndex {
+    Price,
+    PriceFeed,
+    CircuitBreaker,
+    /// Set for any hook policy but `PoolHookPolicy::None`
+    Hook,
+}
+
 #[derive(PartialEq, Eq)]
 pub enum PoolStatusBitFlag {
     Enable,
     Disable,
 }
 
+/// How the launch fee decays to the trade fee of config
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LaunchFeeMode {
+    Linear,
+    Exponential,
+}
+
+impl TryFrom<u8> for LaunchFeeMode {
+    type Error = ();
+
+    fn try_from(value: u8) -> std::result::Result<Self, Self::Error> {
+        match value {
+            0 => Ok(LaunchFeeMode::Linear),
+            1 => Ok(LaunchFeeMode::Exponential),
+            _ => Err(()),
+        }
+    }
+}
+
 #[account(zero_copy(unsafe))]
 #[repr(C, packed)]
 #[derive(Default, Debug)]
@@ -74,17 +113,99 @@ pub struct PoolState {
     pub open_time: u64,
     /// recent epoch
     pub recent_epoch: u64,
+    /// The lp amount locked forever when the pool was created, it's included in `lp_supply`,
+    /// 0 means `DEFAULT_LOCK_LP_AMOUNT` for the pools created before it was recorded
+    pub lock_lp_amount: u64,
+    /// The trade fee rate when the pool opens, decays to the trade fee rate of config
+    /// over `launch_fee_duration` seconds, 0 means no launch fee
+    pub launch_fee_rate: u64,
+    /// Seconds after `open_time` the launch fee decays over
+    pub launch_fee_duration: u32,
+    /// 0: linear decay, 1: exponential decay
+    pub launch_fee_mode: u8,
+    pub padding1: [u8; 3],
+    /// Seconds after `open_time` each swap output is capped by `anti_whale_max_output_bps`
+    pub anti_whale_duration: u32,
+    /// Max output amount of a swap in basis points of the output vault amount without fee,
+    /// 0 means no limit
+    pub anti_whale_max_output_bps: u16,
+    /// Max outflow of each vault within `outflow_window` slots in basis points of the vault
+    /// amount, 0 means no limit
+    pub outflow_limit_bps: u16,
+    /// The `PreOpenAllowList` of wallets can swap before `open_time`, default key means none
+    pub pre_open_allow_list: Pubkey,
+    /// The trade fee rate of swaps from token_0 to token_1, 0 means the trade fee rate of config
+    pub zero_for_one_fee_rate: u64,
+    /// The trade fee rate of swaps from token_1 to token_0, 0 means the trade fee rate of config
+    pub one_for_zero_fee_rate: u64,
+    /// The whole trade fee is taken in the quote mint, which is one of the pool tokens,
+    /// default key means the trade fee is taken in the input token
+    pub quote_mint: Pubkey,
+    /// The accounted token_0 reserve of the pool, excluding the fees and the unsolicited donations
+    pub reserve_0: u64,
+    /// The accounted token_1 reserve of the pool, excluding the fees and the unsolicited donations
+    pub reserve_1: u64,
+    /// Lifetime swap volume of token_0, both swapped in and out
+    pub cumulative_volume_token_0: u128,
+    /// Lifetime swap volume of token_1, both swapped in and out
+    pub cumulative_volume_token_1: u128,
+    /// Lifetime trade fees of token_0 left to the liquidity providers
+    pub cumulative_lp_fees_token_0: u64,
+    /// Lifetime trade fees of token_1 left to the liquidity providers
+    pub cumulative_lp_fees_token_1: u64,
+    /// Lifetime number of swaps
+    pub swap_count: u64,
+    /// Lifetime trade fees of token_0 left to the liquidity providers per lp token, Q64.64
+    pub fee_growth_global_0_x64: u128,
+    /// Lifetime trade fees of token_1 left to the liquidity providers per lp token, Q64.64
+    pub fee_growth_global_1_x64: u128,
+    /// Bits of the enabled `PoolGuardBitIndex`, the side accounts are required once enabled
+    pub guards: u8,
+    pub padding3: [u8; 1],
+    /// Slots the recent outflows of the vaults decay over
+    pub outflow_window: u16,
+    /// The lower 32 bits of the slot the outflows were last recorded
+    pub outflow_last_slot: u32,
+    /// The recent outflow of token_0 vault, in `OUTFLOW_RATE_DENOMINATOR` of the vault amount
+    pub outflow_rate_0: u32,
+    /// The recent outflow of token_1 vault, in `OUTFLOW_RATE_DENOMINATOR` of the vault amount
+    pub outflow_rate_1: u32,
     /// padding for future updates
-    pub padding: [u64; 31],
+    pub padding: [u64; 2],
 }
 
 impl PoolState {
-    pub const LEN: usize = 8 + 10 * 32 + 1 * 5 + 8 * 7 + 8 * 31;
+    pub const LEN: usize = 8
+        + 10 * 32
+        + 1 * 5
+        + 8 * 8
+        + 8
+        + 4
+        + 1
+        + 3
+        + 4
+        + 2
+        + 2
+        + 32
+        + 8 * 2
+        + 32
+        + 8 * 2
+        + 16 * 2
+        + 8 * 3
+        + 16 * 2
+        + 1
+        + 1
+        + 2
+        + 4
+        + 4 * 2
+        + 8 * 2;
 
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize(
         &mut self,
         auth_bump: u8,
         lp_supply: u64,
+        lock_lp_amount: u64,
         open_time: u64,
         pool_creator: Pubkey,
         amm_config: Pubkey,
@@ -116,7 +237,34 @@ impl PoolState {
         self.fund_fees_token_1 = 0;
         self.open_time = open_time;
         self.recent_epoch = Clock::get().unwrap().epoch;
-        self.padding = [0u64; 31];
+        self.lock_lp_amount = lock_lp_amount;
+        self.launch_fee_rate = 0;
+        self.launch_fee_duration = 0;
+        self.launch_fee_mode = 0;
+        self.padding1 = [0u8; 3];
+        self.anti_whale_duration = 0;
+        self.anti_whale_max_output_bps = 0;
+        self.outflow_limit_bps = 0;
+        self.pre_open_allow_list = Pubkey::default();
+        self.zero_for_one_fee_rate = 0;
+        self.one_for_zero_fee_rate = 0;
+        self.quote_mint = Pubkey::default();
+        self.reserve_0 = 0;
+        self.reserve_1 = 0;
+        self.cumulative_volume_token_0 = 0;
+        self.cumulative_volume_token_1 = 0;
+        self.cumulative_lp_fees_token_0 = 0;
+        self.cumulative_lp_fees_token_1 = 0;
+        self.swap_count = 0;
+        self.fee_growth_global_0_x64 = 0;
+        self.fee_growth_global_1_x64 = 0;
+        self.guards = 0;
+        self.padding3 = [0u8; 1];
+        self.outflow_window = 0;
+        self.outflow_last_slot = 0;
+        self.outflow_rate_0 = 0;
+        self.outflow_rate_1 = 0;
+        self.padding = [0u64; 2];
     }
 
     pub fn set_status(&mut self, status: u8) {
@@ -139,7 +287,207 @@ impl PoolState {
         self.status.bitand(status) == 0
     }
 
+    pub fn set_guard_by_bit(&mut self, bit: PoolGuardBitIndex, enabled: bool) {
+        let guard = 1u8 << (bit as u8);
+        if enabled {
+            self.guards |= guard;
+        } else {
+            self.guards &= !guard;
+        }
+    }
+
+    /// Get the guard is enabled or not
+    pub fn get_guard_by_bit(&self, bit: PoolGuardBitIndex) -> bool {
+        self.guards & (1u8 << (bit as u8)) != 0
+    }
+
+    pub fn set_launch_fee(&mut self, launch_fee_rate: u64, duration: u32, mode: LaunchFeeMode) {
+        self.launch_fee_rate = launch_fee_rate;
+        self.launch_fee_duration = duration;
+        self.launch_fee_mode = mode as u8;
+    }
+
+    pub fn set_direction_fee_rates(
+        &mut self,
+        zero_for_one_fee_rate: u64,
+        one_for_zero_fee_rate: u64,
+    ) {
+        self.zero_for_one_fee_rate = zero_for_one_fee_rate;
+        self.one_for_zero_fee_rate = one_for_zero_fee_rate;
+    }
+
+    /// Get the trade fee rate of the trade direction, fall back to the `trade_fee_rate` of config
+    pub fn direction_fee_rate(&self, trade_fee_rate: u64, trade_direction: TradeDirection) -> u64 {
+        let fee_rate = match trade_direction {
+            TradeDirection::ZeroForOne => self.zero_for_one_fee_rate,
+            TradeDirection::OneForZero => self.one_for_zero_fee_rate,
+        };
+        if fee_rate == 0 {
+            trade_fee_rate
+        } else {
+            fee_rate
+        }
+    }
+
+    /// Get the trade fee rate of the trade direction at the block timestamp, which is higher
+    /// than the direction fee rate during the launch phase
+    pub fn trade_fee_rate(
+        &self,
+        trade_fee_rate: u64,
+        trade_direction: TradeDirection,
+        block_timestamp: u64,
+    ) -> u64 {
+        let trade_fee_rate = self.direction_fee_rate(trade_fee_rate, trade_direction);
+        if self.launch_fee_rate == 0 {
+            return trade_fee_rate;
+        }
+        Fees::decayed_fee_rate(
+            self.launch_fee_rate,
+            trade_fee_rate,
+            block_timestamp.saturating_sub(self.open_time),
+            u64::from(self.launch_fee_duration),
+            LaunchFeeMode::try_from(self.launch_fee_mode) == Ok(LaunchFeeMode::Exponential),
+        )
+    }
+
+    /// Check if the trade fee is taken from the output token, which is the case when
+    /// the output token is the quote mint
+    pub fn fee_on_output(&self, trade_direction: TradeDirection) -> bool {
+        if self.quote_mint == Pubkey::default() {
+            return false;
+        }
+        match trade_direction {
+            TradeDirection::ZeroForOne => self.quote_mint == self.token_1_mint,
+            TradeDirection::OneForZero => self.quote_mint == self.token_0_mint,
+        }
+    }
+
+    /// Accumulate the swap volume and the trade fees left to the liquidity providers, in total and
+    /// per lp token, the counters wrap around so the difference of two snapshots is always right
+    pub fn record_swap(
+        &mut self,
+        volume_token_0: u64,
+        volume_token_1: u64,
+        lp_fee_token_0: u64,
+        lp_fee_token_1: u64,
+    ) {
+        self.cumulative_volume_token_0 = self
+            .cumulative_volume_token_0
+            .wrapping_add(u128::from(volume_token_0));
+        self.cumulative_volume_token_1 = self
+            .cumulative_volume_token_1
+            .wrapping_add(u128::from(volume_token_1));
+        self.cumulative_lp_fees_token_0 =
+            self.cumulative_lp_fees_token_0.wrapping_add(lp_fee_token_0);
+        self.cumulative_lp_fees_token_1 =
+            self.cumulative_lp_fees_token_1.wrapping_add(lp_fee_token_1);
+        self.swap_count = self.swap_count.wrapping_add(1);
+        if self.lp_supply != 0 {
+            self.fee_growth_global_0_x64 = self
+                .fee_growth_global_0_x64
+                .wrapping_add((u128::from(lp_fee_token_0) << 64) / u128::from(self.lp_supply));
+            self.fee_growth_global_1_x64 = self
+                .fee_growth_global_1_x64
+                .wrapping_add((u128::from(lp_fee_token_1) << 64) / u128::from(self.lp_supply));
+        }
+    }
+
+    pub fn set_anti_whale(&mut self, duration: u32, max_output_bps: u16) {
+        self.anti_whale_duration = duration;
+        self.anti_whale_max_output_bps = max_output_bps;
+    }
+
+    /// Get the max output amount of a swap at the block timestamp,
+    /// return None if the anti-whale window is not active
+    pub fn anti_whale_max_output(
+        &self,
+        block_timestamp: u64,
+        output_vault_amount: u64,
+    ) -> Option<u64> {
+        if self.anti_whale_max_output_bps == 0
+            || block_timestamp
+                >= self
+                    .open_time
+                    .saturating_add(u64::from(self.anti_whale_duration))
+        {
+            return None;
+        }
+        Some(
+            (u128::from(output_vault_amount) * u128::from(self.anti_whale_max_output_bps)
+                / u128::from(BPS_DENOMINATOR)) as u64,
+        )
+    }
+
+    pub fn set_outflow_limit(&mut self, max_outflow_bps: u16, window: u16) {
+        self.outflow_limit_bps = max_outflow_bps;
+        self.outflow_window = window;
+        self.outflow_rate_0 = 0;
+        self.outflow_rate_1 = 0;
+    }
+
+    /// Record the amounts leaving the vaults at the slot, rejected if the recent outflow of
+    /// either vault exceeds the limit. Each outflow is measured against the vault amount before
+    /// it leaves, and the recent outflows decay linearly to zero over `outflow_window` slots,
+    /// so the limit applies to a rolling window
+    pub fn record_outflow(
+        &mut self,
+        token_0_amount: u64,
+        token_1_amount: u64,
+        vault_0: u64,
+        vault_1: u64,
+        slot: u64,
+    ) -> Result<()> {
+        if self.outflow_limit_bps == 0 {
+            return Ok(());
+        }
+        // only the lower 32 bits are kept, the wrapping difference is exact within 2^32 slots
+        let slot = slot as u32;
+        let elapsed = u64::from(slot.wrapping_sub(self.outflow_last_slot));
+        let window = u64::from(self.outflow_window);
+        let decayed = |rate: u32| {
+            if elapsed >= window {
+                0
+            } else {
+                u64::from(rate) * (window - elapsed) / window
+            }
+        };
+        let outflow_rate = |amount: u64, vault: u64| {
+            if amount == 0 {
+                0
+            } else if vault == 0 {
+                u64::MAX
+            } else {
+                (u128::from(amount) * u128::from(OUTFLOW_RATE_DENOMINATOR))
+                    .div_ceil(u128::from(vault))
+                    .min(u128::from(u64::MAX)) as u64
+            }
+        };
+        let limit =
+            u64::from(self.outflow_limit_bps) * (OUTFLOW_RATE_DENOMINATOR / BPS_DENOMINATOR);
+        let rate_0 =
+            decayed(self.outflow_rate_0).saturating_add(outflow_rate(token_0_amount, vault_0));
+        let rate_1 =
+            decayed(self.outflow_rate_1).saturating_add(outflow_rate(token_1_amount, vault_1));
+        require_gte!(limit, rate_0, ErrorCode::ExceededOutflowLimit);
+        require_gte!(limit, rate_1, ErrorCode::ExceededOutflowLimit);
+        // the rates are within the limit, which is at most OUTFLOW_RATE_DENOMINATOR
+        self.outflow_rate_0 = rate_0 as u32;
+        self.outflow_rate_1 = rate_1 as u32;
+        self.outflow_last_slot = slot;
+        Ok(())
+    }
+
+    /// Get the accounted reserves of the pool, unsolicited donations to the vaults are excluded.
+    /// Pools created before the reserves were accounted fall back to the vault amounts without fees
     pub fn vault_amount_without_fee(&self, vault_0: u64, vault_1: u64) -> (u64, u64) {
+        if self.reserve_0 == 0 && self.reserve_1 == 0 {
+            return self.vault_balance_without_fee(vault_0, vault_1);
+        }
+        (self.reserve_0, self.reserve_1)
+    }
+
+    /// Get the vault balances without the protocol and fund fees, including the donations
+    pub fn vault_balance_without_fee(&self, vault_0: u64, vault_1: u64) -> (u64, u64) {
         (
             vault_0
                 .checked_sub(self.protocol_fees_token_0 + self.fund_fees_token_0)
@@ -150,6 +498,34 @@ impl PoolState {
         )
     }
 
+    /// Get the amounts in the vaults exceed the accounted reserves and fees
+    pub fn surplus_amount(&self, vault_0: u64, vault_1: u64) -> (u64, u64) {
+        let (balance_0, balance_1) = self.vault_balance_without_fee(vault_0, vault_1);
+        let (reserve_0, reserve_1) = self.vault_amount_without_fee(vault_0, vault_1);
+        (
+            balance_0.saturating_sub(reserve_0),
+            balance_1.saturating_sub(reserve_1),
+        )
+    }
+
+    pub fn get_lock_lp_amount(&self) -> u64 {
+        if self.lock_lp_amount == 0 {
+            DEFAULT_LOCK_LP_AMOUNT
+        } else {
+            self.lock_lp_amount
+        }
+    }
+
+    /// Get the lp supply can be burned by withdraws, the locked lp amount is never withdrawn
+    pub fn unlocked_lp_supply(&self) -> u64 {
+        self.lp_supply.saturating_sub(self.get_lock_lp_amount())
+    }
+
+    pub fn set_reserves(&mut self, reserve_0: u64, reserve_1: u64) {
+        self.reserve_0 = reserve_0;
+        self.reserve_1 = reserve_1;
+    }
+
     pub fn token_price_x32(&self, vault_0: u64, vault_1: u64) -> (u128, u128) {
         let (token_0_amount, token_1_amount) = self.vault_amount_without_fee(vault_0, vault_1);
         (
@@ -157,6 +533,46 @@ impl PoolState {
             token_0_amount as u128 * Q32 as u128 / token_1_amount as u128,
         )
     }
+
+    /// Write an observation at the current price of the pool, which is skipped if the update
+    /// duration has not passed since the last observation
+    pub fn observe(
+        &self,
+        observation_state: &mut ObservationState,
+        observation_ticks: Option<&mut ObservationTicks>,
+        vault_0: u64,
+        vault_1: u64,
+        block_timestamp: u64,
+    ) {
+        let (token_0_amount, token_1_amount) = self.vault_amount_without_fee(vault_0, vault_1);
+        if token_0_amount == 0 || token_1_amount == 0 {
+            return;
+        }
+        let (token_0_price_x32, token_1_price_x32) = self.token_price_x32(vault_0, vault_1);
+        observation_state.update(
+            observation_ticks,
+            block_timestamp,
+            token_0_price_x32,
+            token_1_price_x32,
+            oracle::tick_at_reserves(token_0_amount, token_1_amount),
+        );
+    }
+}
+
+/// Calculate the trade fees earned by `lp_amount` lp tokens between two snapshots of
+/// `fee_growth_global_x64` of the same token
+pub fn lp_fees_earned(
+    lp_amount: u64,
+    fee_growth_before_x64: u128,
+    fee_growth_after_x64: u128,
+) -> u64 {
+    let fee_growth_delta_x64 = fee_growth_after_x64.wrapping_sub(fee_growth_before_x64);
+    let fees = (U256::from(fee_growth_delta_x64) * U256::from(lp_amount)) >> 64;
+    if fees > U256::from(u64::MAX) {
+        u64::MAX
+    } else {
+        fees.as_u64()
+    }
 }
 
 #[cfg(test)]
@@ -168,6 +584,321 @@ pub mod pool_test {
         assert_eq!(std::mem::size_of::<PoolState>(), PoolState::LEN - 8)
     }
 
+    #[test]
+    fn launch_trade_fee_rate_test() {
+        let mut pool_state = PoolState {
+            open_time: 1000,
+            ..Default::default()
+        };
+        let direction = TradeDirection::ZeroForOne;
+        assert_eq!(pool_state.trade_fee_rate(2500, direction, 1000), 2500);
+
+        pool_state.set_launch_fee(502_500, 100, LaunchFeeMode::Linear);
+        assert_eq!(pool_state.trade_fee_rate(2500, direction, 1000), 502_500);
+        assert_eq!(pool_state.trade_fee_rate(2500, direction, 1050), 252_500);
+        assert_eq!(pool_state.trade_fee_rate(2500, direction, 1100), 2500);
+
+        pool_state.set_launch_fee(502_500, 100, LaunchFeeMode::Exponential);
+        assert_eq!(pool_state.trade_fee_rate(2500, direction, 1000), 502_500);
+        assert_eq!(pool_state.trade_fee_rate(2500, direction, 1010), 252_500);
+        assert_eq!(pool_state.trade_fee_rate(2500, direction, 1100), 2500);
+    }
+
+    #[test]
+    fn direction_trade_fee_rate_test() {
+        let mut pool_state = PoolState {
+            open_time: 1000,
+            ..Default::default()
+        };
+        assert_eq!(
+            pool_state.trade_fee_rate(2500, TradeDirection::ZeroForOne, 1000),
+            2500
+        );
+        assert_eq!(
+            pool_state.trade_fee_rate(2500, TradeDirection::OneForZero, 1000),
+            2500
+        );
+
+        pool_state.set_direction_fee_rates(10_000, 0);
+        assert_eq!(
+            pool_state.trade_fee_rate(2500, TradeDirection::ZeroForOne, 1000),
+            10_000
+        );
+        assert_eq!(
+            pool_state.trade_fee_rate(2500, TradeDirection::OneForZero, 1000),
+            2500
+        );
+
+        // the launch fee decays to the direction fee rate
+        pool_state.set_launch_fee(510_000, 100, LaunchFeeMode::Linear);
+        assert_eq!(
+            pool_state.trade_fee_rate(2500, TradeDirection::ZeroForOne, 1050),
+            260_000
+        );
+        assert_eq!(
+            pool_state.trade_fee_rate(2500, TradeDirection::ZeroForOne, 1100),
+            10_000
+        );
+        assert_eq!(
+            pool_state.trade_fee_rate(2500, TradeDirection::OneForZero, 1100),
+            2500
+        );
+    }
+
+    #[test]
+    fn fee_on_output_test() {
+        let mut pool_state = PoolState {
+            token_0_mint: Pubkey::new_unique(),
+            token_1_mint: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        assert!(!pool_state.fee_on_output(TradeDirection::ZeroForOne));
+        assert!(!pool_state.fee_on_output(TradeDirection::OneForZero));
+
+        pool_state.quote_mint = pool_state.token_1_mint;
+        assert!(pool_state.fee_on_output(TradeDirection::ZeroForOne));
+        assert!(!pool_state.fee_on_output(TradeDirection::OneForZero));
+
+        pool_state.quote_mint = pool_state.token_0_mint;
+        assert!(!pool_state.fee_on_output(TradeDirection::ZeroForOne));
+        assert!(pool_state.fee_on_output(TradeDirection::OneForZero));
+    }
+
+    #[test]
+    fn reserves_and_surplus_test() {
+        let mut pool_state = PoolState {
+            protocol_fees_token_0: 10,
+            fund_fees_token_1: 20,
+            ..Default::default()
+        };
+        // not accounted yet, fall back to the vault amounts
+        assert_eq!(pool_state.vault_amount_without_fee(1000, 2000), (990, 1980));
+        assert_eq!(pool_state.surplus_amount(1000, 2000), (0, 0));
+
+        pool_state.set_reserves(990, 1980);
+        assert_eq!(pool_state.vault_amount_without_fee(1500, 2000), (990, 1980));
+        assert_eq!(pool_state.surplus_amount(1500, 2100), (500, 100));
+        assert_eq!(pool_state.surplus_amount(900, 2000), (0, 0));
+    }
+
+    #[test]
+    fn legacy_reserves_surplus_test() {
+        let pool_state = PoolState {
+            protocol_fees_token_0: 10,
+            fund_fees_token_1: 20,
+            ..Default::default()
+        };
+        // the whole vaults count as reserves, so the donations can't be skimmed
+        assert_eq!({ pool_state.reserve_0 }, 0);
+        assert_eq!({ pool_state.reserve_1 }, 0);
+        assert_eq!(pool_state.surplus_amount(10, 20), (0, 0));
+        assert_eq!(pool_state.surplus_amount(1_000_000, 2_000_000), (0, 0));
+        assert_eq!(pool_state.surplus_amount(u64::MAX, u64::MAX), (0, 0));
+    }
+
+    #[test]
+    fn donation_skim_and_sync_test() {
+        let mut pool_state = PoolState {
+            protocol_fees_token_0: 10,
+            fund_fees_token_1: 20,
+            ..Default::default()
+        };
+        pool_state.set_reserves(1000, 2000);
+
+        // donate 300 token_0 and 400 token_1 to the vaults
+        let (vault_0, vault_1) = (1010 + 300, 2020 + 400);
+        assert_eq!(pool_state.surplus_amount(vault_0, vault_1), (300, 400));
+
+        // skim sends the surplus out and keeps the reserves
+        let (surplus_0, surplus_1) = pool_state.surplus_amount(vault_0, vault_1);
+        let (vault_0_after, vault_1_after) = (vault_0 - surplus_0, vault_1 - surplus_1);
+        assert_eq!({ pool_state.reserve_0 }, 1000);
+        assert_eq!({ pool_state.reserve_1 }, 2000);
+        assert_eq!(
+            pool_state.vault_amount_without_fee(vault_0_after, vault_1_after),
+            (1000, 2000)
+        );
+        assert_eq!(
+            pool_state.surplus_amount(vault_0_after, vault_1_after),
+            (0, 0)
+        );
+
+        // sync absorbs the donations into the reserves instead
+        let (reserve_0, reserve_1) = pool_state.vault_balance_without_fee(vault_0, vault_1);
+        pool_state.set_reserves(reserve_0, reserve_1);
+        assert_eq!({ pool_state.reserve_0 }, 1300);
+        assert_eq!({ pool_state.reserve_1 }, 2400);
+        assert_eq!(pool_state.surplus_amount(vault_0, vault_1), (0, 0));
+    }
+
+    #[test]
+    fn withdraw_with_transfer_fee_reserves_test() {
+        let mut pool_state = PoolState {
+            protocol_fees_token_0: 10,
+            ..Default::default()
+        };
+        pool_state.set_reserves(1000, 2000);
+        let (vault_0, vault_1) = (1010, 2000);
+
+        // withdraw 100 token_0 and 200 token_1 from mints charging a 1% transfer fee, the vaults
+        // are debited the full amounts and the fees are withheld in the recipient accounts
+        let (token_0_amount, token_1_amount) = (100u64, 200u64);
+        let (receive_0, receive_1) = (token_0_amount - 1, token_1_amount - 2);
+        assert!(receive_0 < token_0_amount && receive_1 < token_1_amount);
+        let (total_0, total_1) = pool_state.vault_amount_without_fee(vault_0, vault_1);
+        pool_state.set_reserves(total_0 - token_0_amount, total_1 - token_1_amount);
+        let (vault_0, vault_1) = (vault_0 - token_0_amount, vault_1 - token_1_amount);
+
+        let (balance_0, balance_1) = pool_state.vault_balance_without_fee(vault_0, vault_1);
+        let (reserve_0, reserve_1) = (pool_state.reserve_0, pool_state.reserve_1);
+        assert!(reserve_0 <= balance_0);
+        assert!(reserve_1 <= balance_1);
+        assert_eq!(pool_state.surplus_amount(vault_0, vault_1), (0, 0));
+    }
+
+    #[test]
+    fn record_swap_test() {
+        let mut pool_state = PoolState::default();
+        pool_state.record_swap(100, 200, 1, 0);
+        pool_state.record_swap(u64::MAX, 50, 0, 2);
+        assert_eq!(
+            { pool_state.cumulative_volume_token_0 },
+            u128::from(u64::MAX) + 100
+        );
+        assert_eq!({ pool_state.cumulative_volume_token_1 }, 250);
+        assert_eq!({ pool_state.cumulative_lp_fees_token_0 }, 1);
+        assert_eq!({ pool_state.cumulative_lp_fees_token_1 }, 2);
+        assert_eq!({ pool_state.swap_count }, 2);
+    }
+
+    #[test]
+    fn lp_fees_earned_test() {
+        let mut pool_state = PoolState {
+            lp_supply: 1000,
+            ..Default::default()
+        };
+        let before = { pool_state.fee_growth_global_0_x64 };
+        pool_state.record_swap(0, 0, 500, 0);
+        pool_state.record_swap(0, 0, 250, 9);
+        let after = { pool_state.fee_growth_global_0_x64 };
+        assert_eq!(lp_fees_earned(1000, before, after), 750);
+        assert_eq!(lp_fees_earned(100, before, after), 75);
+        assert_eq!(lp_fees_earned(100, after, after), 0);
+        // wrap around
+        assert_eq!(lp_fees_earned(1, u128::MAX, Q64 - 1), 1);
+        let growth = pool_state.fee_growth_global_1_x64;
+        assert_eq!(lp_fees_earned(1000, 0, growth), 8);
+    }
+
+    #[test]
+    fn anti_whale_max_output_test() {
+        let mut pool_state = PoolState {
+            open_time: 1000,
+            ..Default::default()
+        };
+        assert_eq!(pool_state.anti_whale_max_output(1000, 1_000_000), None);
+
+        pool_state.set_anti_whale(300, 50);
+        assert_eq!(
+            pool_state.anti_whale_max_output(1000, 1_000_000),
+            Some(5000)
+        );
+        assert_eq!(
+            pool_state.anti_whale_max_output(1299, 1_000_000),
+            Some(5000)
+        );
+        assert_eq!(pool_state.anti_whale_max_output(1300, 1_000_000), None);
+        assert_eq!(
+            pool_state.anti_whale_max_output(1000, u64::MAX),
+            Some(u64::MAX / 200)
+        );
+    }
+
+    #[test]
+    fn unlocked_lp_supply_test() {
+        let mut pool_state = PoolState {
+            lp_supply: 1000,
+            ..Default::default()
+        };
+        // pools created before the locked lp amount was recorded
+        assert_eq!(pool_state.get_lock_lp_amount(), DEFAULT_LOCK_LP_AMOUNT);
+        assert_eq!(pool_state.unlocked_lp_supply(), 900);
+
+        pool_state.lock_lp_amount = 400;
+        assert_eq!(pool_state.unlocked_lp_supply(), 600);
+        pool_state.lp_supply = 300;
+        assert_eq!(pool_state.unlocked_lp_supply(), 0);
+    }
+
+    #[test]
+    fn guard_bits_test() {
+        let mut pool_state = PoolState::default();
+        assert!(!pool_state.get_guard_by_bit(PoolGuardBitIndex::Price));
+        pool_state.set_guard_by_bit(PoolGuardBitIndex::Price, true);
+        assert!(pool_state.get_guard_by_bit(PoolGuardBitIndex::Price));
+        assert_eq!({ pool_state.guards }, 1);
+        pool_state.set_guard_by_bit(PoolGuardBitIndex::Price, false);
+        assert!(!pool_state.get_guard_by_bit(PoolGuardBitIndex::Price));
+        pool_state.set_guard_by_bit(PoolGuardBitIndex::PriceFeed, true);
+        assert!(pool_state.get_guard_by_bit(PoolGuardBitIndex::PriceFeed));
+        assert!(!pool_state.get_guard_by_bit(PoolGuardBitIndex::Price));
+        assert_eq!({ pool_state.guards }, 2);
+        pool_state.set_guard_by_bit(PoolGuardBitIndex::Hook, true);
+        assert!(pool_state.get_guard_by_bit(PoolGuardBitIndex::Hook));
+        assert_eq!({ pool_state.guards }, 10);
+    }
+
+    #[test]
+    fn outflow_limit_test() {
+        let mut pool_state = PoolState::default();
+        // disabled
+        assert!(pool_state
+            .record_outflow(1_000_000, 1_000_000, 1_000_000, 1_000_000, 100)
+            .is_ok());
+
+        // 10% of each vault within 100 slots
+        pool_state.set_outflow_limit(1000, 100);
+        pool_state
+            .record_outflow(60_000, 0, 1_000_000, 1_000_000, 100)
+            .unwrap();
+        let (rate_0, rate_1) = (pool_state.outflow_rate_0, pool_state.outflow_rate_1);
+        assert_eq!((rate_0, rate_1), (6_000_000, 0));
+        // token_1 is tracked separately
+        pool_state
+            .record_outflow(0, 100_000, 1_000_000, 1_000_000, 100)
+            .unwrap();
+        assert!(pool_state
+            .record_outflow(0, 1, 1_000_000, 900_000, 100)
+            .is_err());
+        // exceeded within the same slot
+        assert!(pool_state
+            .record_outflow(50_000, 0, 940_000, 900_000, 100)
+            .is_err());
+        // half decayed after 50 slots
+        pool_state
+            .record_outflow(65_800, 0, 940_000, 900_000, 150)
+            .unwrap();
+        let (rate_0, rate_1) = (pool_state.outflow_rate_0, pool_state.outflow_rate_1);
+        assert_eq!((rate_0, rate_1), (10_000_000, 5_000_000));
+        // fully decayed after the window, including the wrapped slots
+        pool_state.outflow_last_slot = u32::MAX - 10;
+        pool_state
+            .record_outflow(87_000, 0, 870_000, 900_000, (1u64 << 32) + 90)
+            .unwrap();
+        let (rate_0, rate_1) = (pool_state.outflow_rate_0, pool_state.outflow_rate_1);
+        assert_eq!((rate_0, rate_1), (10_000_000, 0));
+        let last_slot = pool_state.outflow_last_slot;
+        assert_eq!(last_slot, 90);
+
+        // updating the limit resets the recent outflows
+        pool_state.set_outflow_limit(500, 100);
+        let rate_0 = pool_state.outflow_rate_0;
+        assert_eq!(rate_0, 0);
+        assert!(pool_state
+            .record_outflow(40_000, 0, 870_000, 900_000, 90)
+            .is_ok());
+    }
+
     mod pool_status_test {
         use super::*;
 
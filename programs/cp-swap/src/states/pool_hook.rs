@@ -0,0 +1,334 @@
+use super::pool::{PoolGuardBitIndex, PoolState};
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+
+pub const POOL_HOOK_SEED: &str = "pool_hook";
+/// Discriminator of the hook instruction, the first 8 bytes of sha256("global:on_pool_action"),
+/// so an anchor program can implement it as `on_pool_action(action: PoolHookAction)`
+pub const POOL_HOOK_DISCRIMINATOR: [u8; 8] = [215, 46, 23, 2, 63, 67, 64, 228];
+
+/// How the actions of the pool treat a failure of the hook. The hook accounts are mandatory
+/// for every action once a hook is registered, so no caller can bypass the hook by omitting them.
+/// The withdraws always use `PoolHookPolicy::Ignore`, so the liquidity can leave the pool
+/// whenever the runtime allows it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoolHookPolicy {
+    /// No hook
+    None,
+    /// A failure of the hook fails the action
+    Abort,
+    /// A failure of the hook is not fatal where the runtime allows it. The runtime aborts the
+    /// transaction on any failed CPI, so only the failures found before the CPI are skipped,
+    /// e.g. the hook program is closed or no longer executable
+    Ignore,
+}
+
+impl PoolHookPolicy {
+    pub fn from_u8(policy: u8) -> Option<Self> {
+        match policy {
+            0 => Some(PoolHookPolicy::None),
+            1 => Some(PoolHookPolicy::Abort),
+            2 => Some(PoolHookPolicy::Ignore),
+            _ => None,
+        }
+    }
+}
+
+/// The program called after each swap, deposit and withdraw of the pool
+#[account]
+#[derive(Default, Debug)]
+pub struct PoolHook {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The pool the hook belongs
+    pub pool_id: Pubkey,
+    /// The program called by the hook instruction
+    pub hook_program: Pubkey,
+    /// The `PoolHookPolicy` of the hook
+    pub policy: u8,
+    /// padding for future updates
+    pub padding: [u64; 8],
+}
+
+impl PoolHook {
+    pub const LEN: usize = 8 + 1 + 32 + 32 + 1 + 8 * 8;
+}
+
+/// Kinds of `PoolHookAction`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoolHookActionKind {
+    Swap,
+    Deposit,
+    Withdraw,
+}
+
+/// The data of the hook instruction after the discriminator, borsh serialized.
+/// The accounts of the hook instruction are:
+///
+/// 0. `[signer]` the `PoolHook` PDA of the pool, to prove the caller is the amm program
+/// 1. `[]` the user of the action
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PoolHookAction {
+    pub pool_id: Pubkey,
+    pub user: Pubkey,
+    /// 0: swap, 1: deposit, 2: withdraw
+    pub kind: u8,
+    /// 0: token_0 in and token_1 out, 1: token_1 in and token_0 out, only for swap
+    pub trade_direction: u8,
+    /// token_0 amount into or out of the pool
+    pub token_0_amount: u64,
+    /// token_1 amount into or out of the pool
+    pub token_1_amount: u64,
+    /// lp amount minted or burned, only for deposit and withdraw
+    pub lp_amount: u64,
+}
+
+impl PoolHookAction {
+    pub fn data(&self) -> Vec<u8> {
+        let mut data = POOL_HOOK_DISCRIMINATOR.to_vec();
+        data.extend(self.try_to_vec().unwrap());
+        data
+    }
+}
+
+/// Call the hook program of the pool if it is registered, the `PoolHook` and the hook program
+/// must be passed by the remaining accounts whatever the policy is
+pub fn invoke_pool_hook<'info>(
+    pool_state: &PoolState,
+    user: &AccountInfo<'info>,
+    action: PoolHookAction,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    if !pool_state.get_guard_by_bit(PoolGuardBitIndex::Hook) {
+        return Ok(());
+    }
+    let (pool_hook_info, pool_hook) = remaining_accounts
+        .iter()
+        .filter(|account_info| account_info.owner == &crate::id())
+        .find_map(|account_info| {
+            let data = account_info.try_borrow_data().ok()?;
+            PoolHook::try_deserialize(&mut &data[..])
+                .ok()
+                .filter(|pool_hook| pool_hook.pool_id == action.pool_id)
+                .map(|pool_hook| (account_info, pool_hook))
+        })
+        .ok_or(ErrorCode::MissingPoolHookAccount)?;
+    let policy = match PoolHookPolicy::from_u8(pool_hook.policy) {
+        Some(PoolHookPolicy::None) | None => return Ok(()),
+        _ if action.kind == PoolHookActionKind::Withdraw as u8 => PoolHookPolicy::Ignore,
+        Some(policy) => policy,
+    };
+    let hook_program_info = remaining_accounts
+        .iter()
+        .find(|account_info| account_info.key == &pool_hook.hook_program)
+        .ok_or(ErrorCode::MissingPoolHookAccount)?;
+    if !hook_program_info.executable {
+        if policy == PoolHookPolicy::Ignore {
+            return Ok(());
+        }
+        return err!(ErrorCode::InvalidPoolHookProgram);
+    }
+    let instruction = Instruction {
+        program_id: pool_hook.hook_program,
+        accounts: vec![
+            AccountMeta::new_readonly(*pool_hook_info.key, true),
+            AccountMeta::new_readonly(*user.key, false),
+        ],
+        data: action.data(),
+    };
+    invoke_signed(
+        &instruction,
+        &[
+            pool_hook_info.clone(),
+            user.clone(),
+            hook_program_info.clone(),
+        ],
+        &[&[
+            POOL_HOOK_SEED.as_bytes(),
+            action.pool_id.as_ref(),
+            &[pool_hook.bump],
+        ]],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod pool_hook_test {
+    use super::*;
+    use anchor_lang::solana_program::hash::hash;
+
+    #[test]
+    fn pool_hook_size_test() {
+        let pool_hook = PoolHook::default();
+        assert_eq!(pool_hook.try_to_vec().unwrap().len(), PoolHook::LEN - 8)
+    }
+
+    #[test]
+    fn pool_hook_action_data_test() {
+        assert_eq!(
+            POOL_HOOK_DISCRIMINATOR,
+            hash(b"global:on_pool_action").to_bytes()[..8]
+        );
+        let action = PoolHookAction {
+            pool_id: Pubkey::new_unique(),
+            user: Pubkey::new_unique(),
+            kind: PoolHookActionKind::Deposit as u8,
+            trade_direction: 0,
+            token_0_amount: 1,
+            token_1_amount: 2,
+            lp_amount: 3,
+        };
+        let data = action.data();
+        assert_eq!(data.len(), 8 + 32 + 32 + 1 + 1 + 8 * 3);
+        assert_eq!(data[..8], POOL_HOOK_DISCRIMINATOR);
+        assert_eq!(data[72], 1);
+        assert_eq!(PoolHookAction::try_from_slice(&data[8..]).unwrap(), action);
+    }
+
+    fn pool_hook_data(pool_id: Pubkey, hook_program: Pubkey, policy: PoolHookPolicy) -> Vec<u8> {
+        let mut data = vec![];
+        PoolHook {
+            pool_id,
+            hook_program,
+            policy: policy as u8,
+            ..PoolHook::default()
+        }
+        .try_serialize(&mut data)
+        .unwrap();
+        data
+    }
+
+    fn invoke_with_accounts<'info>(
+        pool_state: &PoolState,
+        user: &AccountInfo<'info>,
+        action: PoolHookAction,
+        remaining_accounts: &[&AccountInfo<'info>],
+    ) -> Result<()> {
+        let remaining_accounts = remaining_accounts
+            .iter()
+            .map(|account_info| (*account_info).clone())
+            .collect::<Vec<_>>();
+        invoke_pool_hook(pool_state, user, action, &remaining_accounts)
+    }
+
+    #[test]
+    fn invoke_pool_hook_policy_test() {
+        let user_key = Pubkey::new_unique();
+        let system_program = Pubkey::default();
+        let mut user_lamports = 0u64;
+        let mut user_data = vec![];
+        let user = AccountInfo::new(
+            &user_key,
+            true,
+            false,
+            &mut user_lamports,
+            &mut user_data,
+            &system_program,
+            false,
+            0,
+        );
+        // a closed hook program, which is no longer executable
+        let hook_program_key = Pubkey::new_unique();
+        let mut hook_program_lamports = 0u64;
+        let mut hook_program_data = vec![];
+        let hook_program = AccountInfo::new(
+            &hook_program_key,
+            false,
+            false,
+            &mut hook_program_lamports,
+            &mut hook_program_data,
+            &system_program,
+            false,
+            0,
+        );
+        let action = PoolHookAction {
+            pool_id: Pubkey::new_unique(),
+            user: user_key,
+            ..PoolHookAction::default()
+        };
+        let withdraw_action = PoolHookAction {
+            kind: PoolHookActionKind::Withdraw as u8,
+            ..action
+        };
+        let owner = crate::id();
+        let pool_hook_keys = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let mut pool_hook_lamports = [0u64; 3];
+        let mut pool_hook_data = [
+            // the hook of another pool
+            pool_hook_data(
+                Pubkey::new_unique(),
+                hook_program_key,
+                PoolHookPolicy::Abort,
+            ),
+            pool_hook_data(action.pool_id, hook_program_key, PoolHookPolicy::Abort),
+            pool_hook_data(action.pool_id, hook_program_key, PoolHookPolicy::Ignore),
+        ];
+        let [other_pool_hook, abort_pool_hook, ignore_pool_hook] = pool_hook_keys
+            .iter()
+            .zip(pool_hook_lamports.iter_mut())
+            .zip(pool_hook_data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, false, lamports, data, &owner, false, 0)
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+        let is_err =
+            |result: Result<()>, error_code: ErrorCode| result.unwrap_err() == error_code.into();
+
+        // no hook
+        let mut pool_state = PoolState::default();
+        assert!(invoke_with_accounts(&pool_state, &user, action, &[]).is_ok());
+        assert!(invoke_with_accounts(&pool_state, &user, action, &[&other_pool_hook]).is_ok());
+
+        // the hook accounts are mandatory with either policy, for the withdraws too
+        pool_state.set_guard_by_bit(PoolGuardBitIndex::Hook, true);
+        for action in [action, withdraw_action] {
+            for remaining_accounts in [
+                &[][..],
+                &[&other_pool_hook, &hook_program][..],
+                &[&abort_pool_hook][..],
+                &[&ignore_pool_hook][..],
+            ] {
+                assert!(is_err(
+                    invoke_with_accounts(&pool_state, &user, action, remaining_accounts),
+                    ErrorCode::MissingPoolHookAccount
+                ));
+            }
+        }
+
+        // a hook failure found before the call is only fatal with the abort policy
+        assert!(is_err(
+            invoke_with_accounts(
+                &pool_state,
+                &user,
+                action,
+                &[&abort_pool_hook, &hook_program]
+            ),
+            ErrorCode::InvalidPoolHookProgram
+        ));
+        assert!(invoke_with_accounts(
+            &pool_state,
+            &user,
+            action,
+            &[&ignore_pool_hook, &hook_program]
+        )
+        .is_ok());
+        // the withdraws always ignore it
+        assert!(invoke_with_accounts(
+            &pool_state,
+            &user,
+            withdraw_action,
+            &[&abort_pool_hook, &hook_program]
+        )
+        .is_ok());
+    }
+}
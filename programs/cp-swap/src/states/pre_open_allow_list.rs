@@ -0,0 +1,191 @@
+use super::mint_policy::{insert_key, remove_key};
+use crate::error::ErrorCode;
+use crate::utils::AccountLoad;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+pub const PRE_OPEN_ALLOW_LIST_SEED: &str = "pre_open_allow_list";
+pub const PRE_OPEN_PASS_SEED: &str = "pre_open_pass";
+// Number of keys can be stored in PreOpenAllowList
+pub const PRE_OPEN_ALLOW_LIST_KEY_NUM: usize = 64;
+
+/// The wallets allowed to swap before the open time of a pool, listed by keys or
+/// proved against the merkle root with a `PreOpenPass`
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct PreOpenAllowList {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The pool the allow list belongs
+    pub pool_id: Pubkey,
+    /// Root of the merkle tree of allowed wallets, all zero means no merkle tree.
+    /// The leaf is the hash of the wallet key, and the pair of nodes is sorted before hashing
+    pub merkle_root: [u8; 32],
+    /// Allowed wallets, empty slot is Pubkey::default()
+    pub keys: [Pubkey; PRE_OPEN_ALLOW_LIST_KEY_NUM],
+    /// padding for future updates
+    pub padding: [u64; 16],
+}
+
+impl Default for PreOpenAllowList {
+    #[inline]
+    fn default() -> PreOpenAllowList {
+        PreOpenAllowList {
+            bump: 0,
+            pool_id: Pubkey::default(),
+            merkle_root: [0u8; 32],
+            keys: [Pubkey::default(); PRE_OPEN_ALLOW_LIST_KEY_NUM],
+            padding: [0u64; 16],
+        }
+    }
+}
+
+impl PreOpenAllowList {
+    pub const LEN: usize = 8 + 1 + 32 + 32 + 32 * PRE_OPEN_ALLOW_LIST_KEY_NUM + 8 * 16;
+
+    pub fn is_allowed_key(&self, key: &Pubkey) -> bool {
+        *key != Pubkey::default() && self.keys.contains(key)
+    }
+
+    pub fn add_key(&mut self, key: Pubkey) -> Result<()> {
+        insert_key(&mut self.keys, key, ErrorCode::AllowListFull)
+    }
+
+    pub fn remove_key(&mut self, key: Pubkey) -> Result<()> {
+        remove_key(&mut self.keys, key)
+    }
+
+    /// Verify the key is a leaf of the merkle tree
+    pub fn verify_merkle_proof(&self, key: &Pubkey, proof: &[[u8; 32]]) -> bool {
+        if self.merkle_root == [0u8; 32] {
+            return false;
+        }
+        let root = proof
+            .iter()
+            .fold(hashv(&[key.as_ref()]).to_bytes(), |node, sibling| {
+                if node <= *sibling {
+                    hashv(&[&node, sibling]).to_bytes()
+                } else {
+                    hashv(&[sibling, &node]).to_bytes()
+                }
+            });
+        root == self.merkle_root
+    }
+}
+
+/// Issued to a wallet proved to be in the merkle tree of a `PreOpenAllowList`,
+/// it's invalid once the merkle root is changed
+#[account]
+#[derive(Default, Debug)]
+pub struct PreOpenPass {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The allow list the pass belongs
+    pub allow_list: Pubkey,
+    /// The wallet allowed to swap before the open time
+    pub owner: Pubkey,
+    /// The merkle root the wallet was proved against
+    pub merkle_root: [u8; 32],
+}
+
+impl PreOpenPass {
+    pub const LEN: usize = 8 + 1 + 32 + 32 + 32;
+}
+
+/// Check if the trader can swap before the open time of the pool, the allow list
+/// or the trader's `PreOpenPass` is passed by the remaining accounts
+pub fn is_pre_open_trader(
+    allow_list_key: &Pubkey,
+    trader: &Pubkey,
+    remaining_accounts: &[AccountInfo],
+) -> Result<bool> {
+    if *allow_list_key == Pubkey::default() {
+        return Ok(false);
+    }
+    let allow_list_info = match remaining_accounts.iter().find(|a| a.key == allow_list_key) {
+        Some(account_info) => account_info,
+        None => return Ok(false),
+    };
+    let allow_list_loader = AccountLoad::<PreOpenAllowList>::try_from(allow_list_info)?;
+    let allow_list = allow_list_loader.load()?;
+    if allow_list.is_allowed_key(trader) {
+        return Ok(true);
+    }
+    if allow_list.merkle_root == [0u8; 32] {
+        return Ok(false);
+    }
+    for account_info in remaining_accounts {
+        if account_info.owner != &crate::id() || account_info.key == allow_list_key {
+            continue;
+        }
+        let data = account_info.try_borrow_data()?;
+        if let Ok(pass) = PreOpenPass::try_deserialize(&mut &data[..]) {
+            if pass.allow_list == *allow_list_key
+                && pass.owner == *trader
+                && pass.merkle_root == allow_list.merkle_root
+            {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+pub mod pre_open_allow_list_test {
+    use super::*;
+
+    fn hash_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        if a <= b {
+            hashv(&[&a, &b]).to_bytes()
+        } else {
+            hashv(&[&b, &a]).to_bytes()
+        }
+    }
+
+    #[test]
+    fn pre_open_allow_list_size_test() {
+        assert_eq!(
+            std::mem::size_of::<PreOpenAllowList>(),
+            PreOpenAllowList::LEN - 8
+        )
+    }
+
+    #[test]
+    fn allowed_keys_test() {
+        let mut allow_list = PreOpenAllowList::default();
+        let key = Pubkey::new_unique();
+        assert!(!allow_list.is_allowed_key(&key));
+        allow_list.add_key(key).unwrap();
+        assert!(allow_list.is_allowed_key(&key));
+        allow_list.remove_key(key).unwrap();
+        assert!(!allow_list.is_allowed_key(&key));
+
+        for _ in 0..PRE_OPEN_ALLOW_LIST_KEY_NUM {
+            allow_list.add_key(Pubkey::new_unique()).unwrap();
+        }
+        assert!(allow_list.add_key(Pubkey::new_unique()).is_err());
+    }
+
+    #[test]
+    fn verify_merkle_proof_test() {
+        let keys: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let leaves: Vec<[u8; 32]> = keys
+            .iter()
+            .map(|k| hashv(&[k.as_ref()]).to_bytes())
+            .collect();
+        let node_01 = hash_pair(leaves[0], leaves[1]);
+        let node_23 = hash_pair(leaves[2], leaves[3]);
+
+        let mut allow_list = PreOpenAllowList::default();
+        // no merkle tree
+        assert!(!allow_list.verify_merkle_proof(&keys[0], &[leaves[1], node_23]));
+
+        allow_list.merkle_root = hash_pair(node_01, node_23);
+        assert!(allow_list.verify_merkle_proof(&keys[0], &[leaves[1], node_23]));
+        assert!(allow_list.verify_merkle_proof(&keys[3], &[leaves[2], node_01]));
+        assert!(!allow_list.verify_merkle_proof(&keys[0], &[leaves[2], node_23]));
+        assert!(!allow_list.verify_merkle_proof(&Pubkey::new_unique(), &[leaves[1], node_23]));
+    }
+}
@@ -0,0 +1,217 @@
+use super::oracle::{price_x64_at_tick, ObservationState, ObservationTicks};
+use super::pool::{PoolGuardBitIndex, PoolState, Q64};
+use crate::error::ErrorCode;
+use crate::utils::U256;
+use anchor_lang::prelude::*;
+
+pub const PRICE_FEED_SEED: &str = "price_feed";
+/// The exponent of the prices in the price feed, the real price is `price * 10^exponent`
+pub const PRICE_FEED_EXPONENT: i32 = -12;
+
+/// The price of token_0 in token_1 published by a pool, adjusted by the decimals of the mints.
+/// It's refreshed by the swaps and `observe_pool`, which must pass it by the remaining accounts
+#[account]
+#[derive(Default, Debug)]
+pub struct PriceFeed {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The pool the price feed belongs
+    pub pool_id: Pubkey,
+    /// The spot price, the real price is `price * 10^exponent`
+    pub price: i64,
+    /// The absolute deviation of the spot price from the twap, equal to the price if no twap
+    pub confidence: u64,
+    /// The exponent of the price, confidence and twap
+    pub exponent: i32,
+    /// The geometric mean price over `twap_window`, zero if the observations don't cover it
+    pub twap: i64,
+    /// The window of the twap in seconds
+    pub twap_window: u64,
+    /// The slot of the last refresh
+    pub last_updated_slot: u64,
+    /// The unix timestamp of the last refresh
+    pub last_updated_timestamp: i64,
+    /// padding for future updates
+    pub padding: [u64; 8],
+}
+
+impl PriceFeed {
+    pub const LEN: usize = 8 + 1 + 32 + 8 + 8 + 4 + 8 + 8 + 8 + 8 + 8 * 8;
+
+    /// Refresh with the reserves of the pool and the twap tick of the observations
+    pub fn refresh(
+        &mut self,
+        reserve_0: u64,
+        reserve_1: u64,
+        mint_0_decimals: u8,
+        mint_1_decimals: u8,
+        twap_tick: Option<i32>,
+        clock: &Clock,
+    ) {
+        self.price = if reserve_0 == 0 {
+            0
+        } else {
+            decimals_adjusted_price(
+                U256::from(reserve_1),
+                U256::from(reserve_0),
+                mint_0_decimals,
+                mint_1_decimals,
+            )
+        };
+        self.twap = match twap_tick {
+            Some(tick) => decimals_adjusted_price(
+                U256::from(price_x64_at_tick(tick)),
+                U256::from(Q64),
+                mint_0_decimals,
+                mint_1_decimals,
+            ),
+            None => 0,
+        };
+        self.confidence = if self.twap == 0 {
+            self.price.unsigned_abs()
+        } else {
+            self.price.abs_diff(self.twap)
+        };
+        self.exponent = PRICE_FEED_EXPONENT;
+        self.last_updated_slot = clock.slot;
+        self.last_updated_timestamp = clock.unix_timestamp;
+    }
+}
+
+/// Convert the price of raw token amounts `numerator / denominator` to the price of whole
+/// tokens scaled by `10^-PRICE_FEED_EXPONENT`, saturated at i64::MAX
+fn decimals_adjusted_price(
+    numerator: U256,
+    denominator: U256,
+    mint_0_decimals: u8,
+    mint_1_decimals: u8,
+) -> i64 {
+    let scale = i32::from(mint_0_decimals) - i32::from(mint_1_decimals) - PRICE_FEED_EXPONENT;
+    let factor = U256::from(10).checked_pow(U256::from(scale.unsigned_abs()));
+    let price = if scale >= 0 {
+        factor
+            .and_then(|factor| numerator.checked_mul(factor))
+            .map(|numerator| numerator / denominator)
+    } else {
+        Some(
+            factor
+                .and_then(|factor| denominator.checked_mul(factor))
+                .map(|denominator| numerator / denominator)
+                .unwrap_or_default(),
+        )
+    };
+    match price {
+        Some(price) if price <= U256::from(i64::MAX as u64) => price.as_u64() as i64,
+        _ => i64::MAX,
+    }
+}
+
+/// Refresh the price feed of the pool, which must be passed by the remaining accounts as
+/// writable once it's created
+pub fn refresh_price_feed(
+    pool_id: Pubkey,
+    pool_state: &PoolState,
+    observation_state: &ObservationState,
+    observation_ticks: Option<&ObservationTicks>,
+    reserve_0: u64,
+    reserve_1: u64,
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    if !pool_state.get_guard_by_bit(PoolGuardBitIndex::PriceFeed) {
+        return Ok(());
+    }
+    let (price_feed_info, mut price_feed) = remaining_accounts
+        .iter()
+        .filter(|account_info| account_info.owner == &crate::id() && account_info.is_writable)
+        .find_map(|account_info| {
+            let data = account_info.try_borrow_data().ok()?;
+            PriceFeed::try_deserialize(&mut &data[..])
+                .ok()
+                .filter(|price_feed| price_feed.pool_id == pool_id)
+                .map(|price_feed| (account_info, price_feed))
+        })
+        .ok_or(ErrorCode::MissingPriceFeedAccount)?;
+    let price_feed_key = Pubkey::create_program_address(
+        &[
+            PRICE_FEED_SEED.as_bytes(),
+            pool_id.as_ref(),
+            &[price_feed.bump],
+        ],
+        &crate::id(),
+    )
+    .map_err(|_| ErrorCode::MissingPriceFeedAccount)?;
+    require_keys_eq!(
+        *price_feed_info.key,
+        price_feed_key,
+        ErrorCode::MissingPriceFeedAccount
+    );
+    let clock = Clock::get()?;
+    let twap_tick = observation_state.get_twap_tick(observation_ticks, price_feed.twap_window);
+    price_feed.refresh(
+        reserve_0,
+        reserve_1,
+        pool_state.mint_0_decimals,
+        pool_state.mint_1_decimals,
+        twap_tick,
+        &clock,
+    );
+    price_feed.try_serialize(&mut &mut price_feed_info.try_borrow_mut_data()?[..])?;
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod price_feed_test {
+    use super::*;
+    use crate::states::oracle::tick_at_reserves;
+
+    fn clock(slot: u64, unix_timestamp: i64) -> Clock {
+        Clock {
+            slot,
+            unix_timestamp,
+            ..Clock::default()
+        }
+    }
+
+    #[test]
+    fn price_feed_size_test() {
+        let price_feed = PriceFeed::default();
+        assert_eq!(price_feed.try_to_vec().unwrap().len(), PriceFeed::LEN - 8)
+    }
+
+    #[test]
+    fn refresh_test() {
+        let mut price_feed = PriceFeed::default();
+        // 100 token_0 with 9 decimals for 250 token_1 with 6 decimals
+        price_feed.refresh(100_000_000_000, 250_000_000, 9, 6, None, &clock(10, 20));
+        assert_eq!(price_feed.price, 2_500_000_000_000);
+        assert_eq!(price_feed.exponent, PRICE_FEED_EXPONENT);
+        assert_eq!(price_feed.twap, 0);
+        assert_eq!(price_feed.confidence, 2_500_000_000_000);
+        assert_eq!(price_feed.last_updated_slot, 10);
+        assert_eq!(price_feed.last_updated_timestamp, 20);
+
+        // the twap tick of the same reserves, which is rounded down
+        let tick = tick_at_reserves(100_000_000_000, 250_000_000);
+        price_feed.refresh(
+            100_000_000_000,
+            250_000_000,
+            9,
+            6,
+            Some(tick),
+            &clock(11, 21),
+        );
+        assert!(price_feed.twap <= price_feed.price);
+        assert!(price_feed.twap.abs_diff(2_500_000_000_000) < 250_000_000);
+        assert_eq!(
+            price_feed.confidence,
+            price_feed.price.abs_diff(price_feed.twap)
+        );
+
+        // too small to be represented
+        price_feed.refresh(u64::MAX, 1, 0, 18, None, &clock(12, 22));
+        assert_eq!(price_feed.price, 0);
+        // saturated
+        price_feed.refresh(1, u64::MAX, 18, 0, None, &clock(12, 22));
+        assert_eq!(price_feed.price, i64::MAX);
+    }
+}
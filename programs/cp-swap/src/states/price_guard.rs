@@ -0,0 +1,234 @@
+use super::pool::{PoolGuardBitIndex, PoolState, BPS_DENOMINATOR};
+use crate::error::ErrorCode;
+use crate::utils::U256;
+use anchor_lang::prelude::*;
+
+pub const PRICE_GUARD_SEED: &str = "price_guard";
+/// Length of the reference price account data
+pub const REFERENCE_PRICE_LEN: usize = 8 + 4 + 8;
+
+/// Rejects the swaps pushing the pool price too far from an external reference price
+#[account]
+#[derive(Default, Debug)]
+pub struct PriceGuard {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// The pool the price guard belongs
+    pub pool_id: Pubkey,
+    /// The account of the reference price, see `ReferencePrice` for the layout
+    pub reference_price: Pubkey,
+    /// Max deviation in basis points of the pool price after a swap from the reference price
+    pub max_deviation_bps: u16,
+    /// Max seconds since the reference price was published
+    pub max_staleness: u32,
+    /// padding for future updates
+    pub padding: [u64; 8],
+}
+
+impl PriceGuard {
+    pub const LEN: usize = 8 + 1 + 32 + 32 + 2 + 4 + 8 * 8;
+}
+
+/// The reference price of token_0 in token_1 of whole tokens, read from the first
+/// `REFERENCE_PRICE_LEN` bytes of the reference price account, little endian:
+///
+/// | offset | type | field        |
+/// |--------|------|--------------|
+/// | 0      | i64  | price        |
+/// | 8      | i32  | exponent     |
+/// | 12     | i64  | publish_time |
+///
+/// The real price is `price * 10^exponent`, `publish_time` is the unix timestamp
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReferencePrice {
+    pub price: i64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+impl ReferencePrice {
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() < REFERENCE_PRICE_LEN {
+            return None;
+        }
+        Some(Self {
+            price: i64::from_le_bytes(data[0..8].try_into().unwrap()),
+            exponent: i32::from_le_bytes(data[8..12].try_into().unwrap()),
+            publish_time: i64::from_le_bytes(data[12..20].try_into().unwrap()),
+        })
+    }
+
+    pub fn to_bytes(&self) -> [u8; REFERENCE_PRICE_LEN] {
+        let mut data = [0u8; REFERENCE_PRICE_LEN];
+        data[0..8].copy_from_slice(&self.price.to_le_bytes());
+        data[8..12].copy_from_slice(&self.exponent.to_le_bytes());
+        data[12..20].copy_from_slice(&self.publish_time.to_le_bytes());
+        data
+    }
+
+    /// Get the deviation in basis points of the price of the reserves from the reference price,
+    /// return None if it can't be calculated
+    pub fn deviation_bps(
+        &self,
+        reserve_0: u64,
+        reserve_1: u64,
+        mint_0_decimals: u8,
+        mint_1_decimals: u8,
+    ) -> Option<u64> {
+        if self.price <= 0 || reserve_0 == 0 {
+            return None;
+        }
+        let ten_pow = |n: u32| U256::from(10).checked_pow(U256::from(n));
+        // pool price: reserve_1 * 10^mint_0_decimals / (reserve_0 * 10^mint_1_decimals)
+        let pool_numerator =
+            U256::from(reserve_1).checked_mul(ten_pow(u32::from(mint_0_decimals))?)?;
+        let pool_denominator =
+            U256::from(reserve_0).checked_mul(ten_pow(u32::from(mint_1_decimals))?)?;
+        // reference price: price * 10^exponent
+        let (reference_numerator, reference_denominator) = if self.exponent >= 0 {
+            (
+                U256::from(self.price as u64)
+                    .checked_mul(ten_pow(self.exponent.unsigned_abs())?)?,
+                U256::one(),
+            )
+        } else {
+            (
+                U256::from(self.price as u64),
+                ten_pow(self.exponent.unsigned_abs())?,
+            )
+        };
+        let pool_value = pool_numerator.checked_mul(reference_denominator)?;
+        let reference_value = reference_numerator.checked_mul(pool_denominator)?;
+        let deviation_bps = if pool_value > reference_value {
+            pool_value - reference_value
+        } else {
+            reference_value - pool_value
+        }
+        .checked_mul(U256::from(BPS_DENOMINATOR))?
+            / reference_value;
+        if deviation_bps > U256::from(u64::MAX) {
+            Some(u64::MAX)
+        } else {
+            Some(deviation_bps.as_u64())
+        }
+    }
+}
+
+/// Check the price of the reserves after a swap against the reference price if the price guard
+/// of the pool is enabled, the price guard and the reference price are passed by the remaining
+/// accounts
+pub fn check_price_guard(
+    pool_id: Pubkey,
+    pool_state: &PoolState,
+    remaining_accounts: &[AccountInfo],
+    block_timestamp: u64,
+) -> Result<()> {
+    if !pool_state.get_guard_by_bit(PoolGuardBitIndex::Price) {
+        return Ok(());
+    }
+    let price_guard = remaining_accounts
+        .iter()
+        .filter(|account_info| account_info.owner == &crate::id())
+        .find_map(|account_info| {
+            let data = account_info.try_borrow_data().ok()?;
+            PriceGuard::try_deserialize(&mut &data[..])
+                .ok()
+                .filter(|price_guard| price_guard.pool_id == pool_id)
+        })
+        .ok_or(ErrorCode::MissingPriceGuardAccount)?;
+    let reference_price_info = remaining_accounts
+        .iter()
+        .find(|account_info| account_info.key == &price_guard.reference_price)
+        .ok_or(ErrorCode::MissingPriceGuardAccount)?;
+    let reference_price = ReferencePrice::parse(&reference_price_info.try_borrow_data()?)
+        .ok_or(ErrorCode::InvalidReferencePrice)?;
+    if block_timestamp.saturating_sub(reference_price.publish_time.max(0) as u64)
+        > u64::from(price_guard.max_staleness)
+    {
+        return err!(ErrorCode::StaleReferencePrice);
+    }
+    let deviation_bps = reference_price
+        .deviation_bps(
+            pool_state.reserve_0,
+            pool_state.reserve_1,
+            pool_state.mint_0_decimals,
+            pool_state.mint_1_decimals,
+        )
+        .ok_or(ErrorCode::InvalidReferencePrice)?;
+    require_gte!(
+        u64::from(price_guard.max_deviation_bps),
+        deviation_bps,
+        ErrorCode::ExceededReferencePriceDeviation
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod price_guard_test {
+    use super::*;
+
+    #[test]
+    fn price_guard_size_test() {
+        let price_guard = PriceGuard::default();
+        assert_eq!(price_guard.try_to_vec().unwrap().len(), PriceGuard::LEN - 8)
+    }
+
+    #[test]
+    fn reference_price_layout_test() {
+        let reference_price = ReferencePrice {
+            price: 2_500_000,
+            exponent: -6,
+            publish_time: 1_700_000_000,
+        };
+        let data = reference_price.to_bytes();
+        assert_eq!(data[0..8], 2_500_000i64.to_le_bytes());
+        assert_eq!(data[8..12], (-6i32).to_le_bytes());
+        assert_eq!(ReferencePrice::parse(&data), Some(reference_price));
+        assert_eq!(
+            ReferencePrice::parse(&data[..REFERENCE_PRICE_LEN - 1]),
+            None
+        );
+    }
+
+    #[test]
+    fn deviation_bps_test() {
+        // 2.5 token_1 per token_0
+        let reference_price = ReferencePrice {
+            price: 2_500_000,
+            exponent: -6,
+            publish_time: 0,
+        };
+        // 100 token_0 with 9 decimals for 250 token_1 with 6 decimals
+        assert_eq!(
+            reference_price.deviation_bps(100_000_000_000, 250_000_000, 9, 6),
+            Some(0)
+        );
+        assert_eq!(
+            reference_price.deviation_bps(100_000_000_000, 262_500_000, 9, 6),
+            Some(500)
+        );
+        assert_eq!(
+            reference_price.deviation_bps(100_000_000_000, 237_500_000, 9, 6),
+            Some(500)
+        );
+        let reference_price = ReferencePrice {
+            price: 25,
+            exponent: -1,
+            publish_time: 0,
+        };
+        assert_eq!(
+            reference_price.deviation_bps(100_000_000_000, 250_000_000, 9, 6),
+            Some(0)
+        );
+        // invalid
+        let reference_price = ReferencePrice {
+            price: 0,
+            exponent: 0,
+            publish_time: 0,
+        };
+        assert_eq!(
+            reference_price.deviation_bps(100_000_000_000, 250_000_000, 9, 6),
+            None
+        );
+    }
+}
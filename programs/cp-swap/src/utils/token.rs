@@ -1,6 +1,9 @@
 use crate::error::ErrorCode;
+use crate::states::MintPolicy;
+use crate::utils::AccountLoad;
 use anchor_lang::{prelude::*, system_program};
 use anchor_spl::{
+    metadata::{mpl_token_metadata, Metadata as MetadataProgram},
     token::{Token, TokenAccount},
     token_2022,
     token_interface::{initialize_account3, InitializeAccount3, Mint},
@@ -9,30 +12,42 @@ use spl_token_2022::{
     self,
     extension::{
         transfer_fee::{TransferFeeConfig, MAX_FEE_BASIS_POINTS},
-        BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+        transfer_hook, BaseStateWithExtensions, ExtensionType, StateWithExtensions,
     },
 };
-use std::collections::HashSet;
-
-const MINT_WHITELIST: [&'static str; 4] = [
-    "HVbpJAQGNpkgBaYBZQBR1t7yFdvaYVp2vCQQfKKEN4tM",
-    "Crn4x1Y2HUKko7ox2EZMT6N2t2ZyH7eKtwkBGVnhEq1g",
-    "FrBfWJ4qE5sCzKm3k3JaAtqZcXUh4LvJygDeketsrsH4",
-    "2b1kV6DkPAnxd5ixfnxCpjxmKwqjjaYmCZfHsFu24GXo",
-];
+use spl_token_metadata_interface::state::TokenMetadata;
 
+/// Transfer from user to pool vault, the extra accounts required by a transfer hook mint
+/// and the mint policy are resolved from `remaining_accounts`
+#[allow(clippy::too_many_arguments)]
 pub fn transfer_from_user_to_pool_vault<'a>(
     authority: AccountInfo<'a>,
     from: AccountInfo<'a>,
     to_vault: AccountInfo<'a>,
     mint: AccountInfo<'a>,
     token_program: AccountInfo<'a>,
+    remaining_accounts: &[AccountInfo<'a>],
     amount: u64,
     mint_decimals: u8,
 ) -> Result<()> {
     if amount == 0 {
         return Ok(());
     }
+    if let Some(hook_program_id) = get_transfer_hook_program_id(&mint)? {
+        check_transfer_hook_program(&mint, &hook_program_id, remaining_accounts)?;
+        spl_token_2022::onchain::invoke_transfer_checked(
+            token_program.key,
+            from,
+            mint,
+            to_vault,
+            authority,
+            remaining_accounts,
+            amount,
+            mint_decimals,
+            &[],
+        )?;
+        return Ok(());
+    }
     token_2022::transfer_checked(
         CpiContext::new(
             token_program.to_account_info(),
@@ -48,12 +63,16 @@ pub fn transfer_from_user_to_pool_vault<'a>(
     )
 }
 
+/// Transfer from pool vault to user, the extra accounts required by a transfer hook mint
+/// and the mint policy are resolved from `remaining_accounts`
+#[allow(clippy::too_many_arguments)]
 pub fn transfer_from_pool_vault_to_user<'a>(
     authority: AccountInfo<'a>,
     from_vault: AccountInfo<'a>,
     to: AccountInfo<'a>,
     mint: AccountInfo<'a>,
     token_program: AccountInfo<'a>,
+    remaining_accounts: &[AccountInfo<'a>],
     amount: u64,
     mint_decimals: u8,
     signer_seeds: &[&[&[u8]]],
@@ -61,6 +80,21 @@ pub fn transfer_from_pool_vault_to_user<'a>(
     if amount == 0 {
         return Ok(());
     }
+    if let Some(hook_program_id) = get_transfer_hook_program_id(&mint)? {
+        check_transfer_hook_program(&mint, &hook_program_id, remaining_accounts)?;
+        spl_token_2022::onchain::invoke_transfer_checked(
+            token_program.key,
+            from_vault,
+            mint,
+            to,
+            authority,
+            remaining_accounts,
+            amount,
+            mint_decimals,
+            signer_seeds,
+        )?;
+        return Ok(());
+    }
     token_2022::transfer_checked(
         CpiContext::new_with_signer(
             token_program.to_account_info(),
@@ -175,25 +209,111 @@ pub fn get_transfer_fee(mint_info: &AccountInfo, pre_fee_amount: u64) -> Result<
     Ok(fee)
 }
 
-pub fn is_supported_mint(mint_account: &InterfaceAccount<Mint>) -> Result<bool> {
+/// Get the decimals of the mint, which is owned by token program or token_2022 program
+pub fn get_mint_decimals(mint_info: &AccountInfo) -> Result<u8> {
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    Ok(mint.base.decimals)
+}
+
+/// Get the transfer hook program of the mint, return None if the mint has no transfer hook
+pub fn get_transfer_hook_program_id(mint_info: &AccountInfo) -> Result<Option<Pubkey>> {
+    if *mint_info.owner == Token::id() {
+        return Ok(None);
+    }
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    Ok(transfer_hook::get_program_id(&mint))
+}
+
+/// Check the transfer hook program of the mint against the mint policy passed by
+/// `remaining_accounts`, since the hook authority can change the program after the pool
+/// was created
+pub fn check_transfer_hook_program(
+    mint_info: &AccountInfo,
+    hook_program_id: &Pubkey,
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    let mint_policy_info = remaining_accounts
+        .iter()
+        .find(|account_info| AccountLoad::<MintPolicy>::try_from(account_info).is_ok())
+        .ok_or(ErrorCode::MissingMintPolicyAccount)?;
+    let mint_policy_loader = AccountLoad::<MintPolicy>::try_from(mint_policy_info)?;
+    let mint_policy = mint_policy_loader.load()?;
+    if mint_policy.is_denied_mint(mint_info.key)
+        || !(mint_policy.is_allowed_mint(mint_info.key)
+            || mint_policy.is_allowed_hook_program(hook_program_id))
+    {
+        return err!(ErrorCode::NotSupportMint);
+    }
+    Ok(())
+}
+
+/// Get the symbol of the mint, read from the token_2022 metadata extension or the metaplex
+/// metadata account, fallback to the first chars of the mint address if no symbol is found
+pub fn get_token_symbol(
+    mint_info: &AccountInfo,
+    metadata_info: Option<&AccountInfo>,
+) -> Result<String> {
+    let mut symbol = String::new();
+    if *mint_info.owner == token_2022::Token2022::id() {
+        let mint_data = mint_info.try_borrow_data()?;
+        let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+        if let Ok(token_metadata) = mint.get_variable_len_extension::<TokenMetadata>() {
+            symbol = token_metadata.symbol;
+        }
+    }
+    if symbol.trim().is_empty() {
+        if let Some(metadata_info) = metadata_info {
+            let (metadata_key, _) = mpl_token_metadata::accounts::Metadata::find_pda(mint_info.key);
+            require_keys_eq!(metadata_key, metadata_info.key(), ErrorCode::InvalidInput);
+            if *metadata_info.owner == MetadataProgram::id() {
+                let metadata = mpl_token_metadata::accounts::Metadata::safe_deserialize(
+                    &metadata_info.try_borrow_data()?,
+                )?;
+                symbol = metadata.symbol;
+            }
+        }
+    }
+    let symbol = symbol.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+    if symbol.is_empty() {
+        return Ok(mint_info.key.to_string()[..4].to_string());
+    }
+    Ok(symbol.to_string())
+}
+
+/// Check the mint against the admin managed `MintPolicy`.
+/// Denied mints are always rejected, spl token mints and allowed mints are always accepted,
+/// other token_2022 mints are accepted only if all of their extensions are allowed.
+/// A transfer hook is allowed when its program is in the policy's hook program list.
+pub fn is_supported_mint(
+    mint_account: &InterfaceAccount<Mint>,
+    mint_policy: &MintPolicy,
+) -> Result<bool> {
+    let mint_key = mint_account.key();
+    if mint_policy.is_denied_mint(&mint_key) {
+        return Ok(false);
+    }
     let mint_info = mint_account.to_account_info();
     if *mint_info.owner == Token::id() {
         return Ok(true);
     }
-    let mint_whitelist: HashSet<&str> = MINT_WHITELIST.into_iter().collect();
-    if mint_whitelist.contains(mint_account.key().to_string().as_str()) {
+    if mint_policy.is_allowed_mint(&mint_key) {
         return Ok(true);
     }
     let mint_data = mint_info.try_borrow_data()?;
     let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
     let extensions = mint.get_extension_types()?;
     for e in extensions {
-        if e != ExtensionType::TransferFeeConfig
-            && e != ExtensionType::MetadataPointer
-            && e != ExtensionType::TokenMetadata
-            && e != ExtensionType::InterestBearingConfig
-            && e != ExtensionType::ScaledUiAmount
-        {
+        if e == ExtensionType::TransferHook {
+            if let Some(program_id) = transfer_hook::get_program_id(&mint) {
+                if !mint_policy.is_allowed_hook_program(&program_id) {
+                    return Ok(false);
+                }
+                continue;
+            }
+        }
+        if !mint_policy.is_extension_allowed(e) {
             return Ok(false);
         }
     }
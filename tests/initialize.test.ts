@@ -3,7 +3,13 @@ import { Program, BN } from "@coral-xyz/anchor";
 import { RaydiumCpSwap } from "../target/types/raydium_cp_swap";
 
 import { getAccount, TOKEN_PROGRAM_ID } from "@solana/spl-token";
-import { setupInitializeTest, initialize, calculateFee } from "./utils";
+import {
+  setupInitializeTest,
+  initialize,
+  calculateFee,
+  getMetadataAddress,
+  METADATA_PROGRAM_ID,
+} from "./utils";
 import { assert } from "chai";
 
 describe("initialize test", () => {
@@ -62,6 +68,13 @@ describe("initialize test", () => {
       poolState.token1Program
     );
     assert.equal(vault1.amount.toString(), initAmount1.toString());
+
+    const [lpMintMetadata] = await getMetadataAddress(poolState.lpMint);
+    const lpMintMetadataInfo = await anchor
+      .getProvider()
+      .connection.getAccountInfo(lpMintMetadata, "processed");
+    assert.isNotNull(lpMintMetadataInfo);
+    assert.isTrue(lpMintMetadataInfo.owner.equals(METADATA_PROGRAM_ID));
   });
 
   it("create pool with fee", async () => {
@@ -24,6 +24,10 @@ import {
   getPoolVaultAddress,
   createTokenMintAndAssociatedTokenAccount,
   getOrcleAccountAddress,
+  getMintPolicyAddress,
+  getLpMetadataConfigAddress,
+  getMetadataAddress,
+  METADATA_PROGRAM_ID,
 } from "./index";
 
 import { ASSOCIATED_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/utils/token";
@@ -63,6 +67,8 @@ export async function setupInitializeTest(
     config.create_fee,
     confirmOptions
   );
+  await createMintPolicy(program, connection, owner, confirmOptions);
+  await createLpMetadataConfig(program, connection, owner, confirmOptions);
   return {
     configAddress,
     token0,
@@ -108,6 +114,8 @@ export async function setupDepositTest(
     config.create_fee,
     confirmOptions
   );
+  await createMintPolicy(program, connection, owner, confirmOptions);
+  await createLpMetadataConfig(program, connection, owner, confirmOptions);
 
   while (1) {
     const [{ token0, token0Program }, { token1, token1Program }] =
@@ -179,6 +187,8 @@ export async function setupSwapTest(
     config.create_fee,
     confirmOptions
   );
+  await createMintPolicy(program, connection, owner, confirmOptions);
+  await createLpMetadataConfig(program, connection, owner, confirmOptions);
 
   const [{ token0, token0Program }, { token1, token1Program }] =
     await createTokenMintAndAssociatedTokenAccount(
@@ -254,6 +264,62 @@ export async function createAmmConfig(
   return address;
 }
 
+// TransferFeeConfig, InterestBearingConfig, MetadataPointer, TokenMetadata, ScaledUiAmount
+export const DEFAULT_ALLOWED_EXTENSIONS = new BN(
+  (1 << 1) | (1 << 10) | (1 << 18) | (1 << 19) | (1 << 25)
+);
+
+export async function createMintPolicy(
+  program: Program<RaydiumCpSwap>,
+  connection: Connection,
+  owner: Signer,
+  confirmOptions?: ConfirmOptions,
+  allowedExtensions: BN = DEFAULT_ALLOWED_EXTENSIONS
+): Promise<PublicKey> {
+  const [address, _] = await getMintPolicyAddress(program.programId);
+  if (await accountExist(connection, address)) {
+    return address;
+  }
+
+  const ix = await program.methods
+    .createMintPolicy(allowedExtensions)
+    .accounts({
+      owner: owner.publicKey,
+    })
+    .instruction();
+
+  const tx = await sendTransaction(connection, [ix], [owner], confirmOptions);
+  console.log("init mint policy tx: ", tx);
+  return address;
+}
+
+export const DEFAULT_LP_METADATA_URI_TEMPLATE =
+  "https://example.com/cp-lp/{lp_mint}.json";
+
+export async function createLpMetadataConfig(
+  program: Program<RaydiumCpSwap>,
+  connection: Connection,
+  owner: Signer,
+  confirmOptions?: ConfirmOptions,
+  uriTemplate: string = DEFAULT_LP_METADATA_URI_TEMPLATE
+): Promise<PublicKey> {
+  const [address, _] = await getLpMetadataConfigAddress(program.programId);
+  if (await accountExist(connection, address)) {
+    return address;
+  }
+
+  const ix = await program.methods
+    .createLpMetadataConfig(uriTemplate)
+    .accounts({
+      owner: owner.publicKey,
+    })
+    .instruction();
+
+  const tx = await sendTransaction(connection, [ix], [owner], confirmOptions);
+  console.log("init lp metadata config tx: ", tx);
+  return address;
+}
+
 export async function initialize(
   program: Program<RaydiumCpSwap>,
   creator: Signer,
@@ -303,6 +369,9 @@ export async function initialize(
     poolAddress,
     program.programId
   );
+  const [lpMintMetadata] = await getMetadataAddress(lpMintAddress);
+  const [token0Metadata] = await getMetadataAddress(token0);
+  const [token1Metadata] = await getMetadataAddress(token1);
 
   const creatorToken0 = getAssociatedTokenAddressSync(
     token0,
@@ -325,7 +394,10 @@ export async function initialize(
       poolState: poolAddress,
       token0Mint: token0,
       token1Mint: token1,
+      token0Metadata,
+      token1Metadata,
       lpMint: lpMintAddress,
+      lpMintMetadata,
       creatorToken0,
       creatorToken1,
       creatorLpToken: creatorLpTokenAddress,
@@ -336,6 +408,7 @@ export async function initialize(
       tokenProgram: TOKEN_PROGRAM_ID,
       token0Program: token0Program,
       token1Program: token1Program,
+      metadataProgram: METADATA_PROGRAM_ID,
       systemProgram: SystemProgram.programId,
       rent: SYSVAR_RENT_PUBKEY,
     })
@@ -25,6 +25,18 @@ export const ORACLE_SEED = Buffer.from(
   anchor.utils.bytes.utf8.encode("observation")
 );
 
+export const MINT_POLICY_SEED = Buffer.from(
+  anchor.utils.bytes.utf8.encode("mint_policy")
+);
+
+export const LP_METADATA_CONFIG_SEED = Buffer.from(
+  anchor.utils.bytes.utf8.encode("lp_metadata_config")
+);
+
+export const METADATA_PROGRAM_ID = new PublicKey(
+  "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+);
+
 export function u16ToBytes(num: number) {
   const arr = new ArrayBuffer(2);
   const view = new DataView(arr);
@@ -125,3 +137,37 @@ export async function getOrcleAccountAddress(
   );
   return [address, bump];
 }
+
+export async function getMintPolicyAddress(
+  programId: PublicKey
+): Promise<[PublicKey, number]> {
+  const [address, bump] = await PublicKey.findProgramAddress(
+    [MINT_POLICY_SEED],
+    programId
+  );
+  return [address, bump];
+}
+
+export async function getLpMetadataConfigAddress(
+  programId: PublicKey
+): Promise<[PublicKey, number]> {
+  const [address, bump] = await PublicKey.findProgramAddress(
+    [LP_METADATA_CONFIG_SEED],
+    programId
+  );
+  return [address, bump];
+}
+
+export async function getMetadataAddress(
+  mint: PublicKey
+): Promise<[PublicKey, number]> {
+  const [address, bump] = await PublicKey.findProgramAddress(
+    [
+      anchor.utils.bytes.utf8.encode("metadata"),
+      METADATA_PROGRAM_ID.toBuffer(),
+      mint.toBuffer(),
+    ],
+    METADATA_PROGRAM_ID
+  );
+  return [address, bump];
+}